@@ -0,0 +1,121 @@
+//! Finds the `src/dayN.rs` modules and generates the code registering them, so
+//! that adding a day is just a matter of adding its file. Also hashes the
+//! rest of the crate's source, so cached answers can tell when code every day
+//! depends on has changed.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    env, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The parts a day module may leave out; they are reported as unsolved.
+const PARTS: [&str; 2] = ["part1", "part2"];
+
+/// The code `solution!` includes to solve a part: a closure calling the
+/// module's function if it has one, or reporting the part unsolved if not.
+/// It binds its own parameters, as the macro's aren't visible to it.
+const CALL: &str = "|puzzle: &crate::solution::Puzzle, ctx: &crate::context::Context| \
+    -> ::anyhow::Result<crate::answer::Answer> { crate::solution::call_part(parse, {}, puzzle, ctx) }";
+const STUB: &str = "|_: &crate::solution::Puzzle, _: &crate::context::Context| \
+    -> ::anyhow::Result<crate::answer::Answer> { Ok(crate::answer::Answer::Unsolved) }";
+
+fn main() -> io::Result<()> {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    let root = src.parent().unwrap().to_owned();
+    println!("cargo::rerun-if-changed={}", src.display());
+    for file in ["build.rs", "Cargo.toml", "Cargo.lock"] {
+        println!("cargo::rerun-if-changed={}", root.join(file).display());
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let mut days = vec![];
+    for entry in fs::read_dir(&src)? {
+        let path = entry?.path();
+        let Some(day) = path.file_name().and_then(|n| n.to_str()).and_then(|n| {
+            n.strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse::<u32>()
+                .ok()
+        }) else {
+            continue;
+        };
+
+        // Commented-out code doesn't count.
+        let text: String = fs::read_to_string(&path)?
+            .lines()
+            .filter(|l| !l.trim_start().starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !text.contains("fn parse(") {
+            println!(
+                "cargo::warning=skipping day {}: src/day{}.rs has no parse function",
+                day, day
+            );
+            continue;
+        }
+        if !text.contains(&format!("solution!({},", day)) {
+            println!(
+                "cargo::warning=skipping day {}: src/day{}.rs does not invoke solution!({}, ...)",
+                day, day, day
+            );
+            continue;
+        }
+        for part in PARTS {
+            let code = match text.contains(&format!("fn {}(", part)) {
+                true => CALL.replace("{}", part),
+                false => {
+                    println!(
+                        "cargo::warning=day {}: src/day{}.rs has no {}; it will be reported as unsolved",
+                        day, day, part
+                    );
+                    STUB.to_owned()
+                }
+            };
+            fs::write(out.join(format!("day{}_{}.rs", day, part)), code)?;
+        }
+        days.push((day, path));
+    }
+    days.sort();
+
+    let mut code = String::from("solution::register! {\n");
+    for (day, path) in &days {
+        code += &format!("    day{} = {:?},\n", day, path.display().to_string());
+    }
+    code += "}\n";
+
+    let mut hasher = DefaultHasher::new();
+    let mut shared = files(&src)?;
+    shared.retain(|f| !days.iter().any(|(_, day)| day == f));
+    shared.extend(["build.rs", "Cargo.toml", "Cargo.lock"].map(|f| root.join(f)));
+    for path in &shared {
+        path.strip_prefix(&root).unwrap_or(path).hash(&mut hasher);
+        match fs::read(path) {
+            Ok(bytes) => bytes.hash(&mut hasher),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    code += &format!(
+        "\n/// Hash of the crate's source other than the day modules.\npub(crate) const SHARED_SOURCE_HASH: u64 = {:#x};\n",
+        hasher.finish()
+    );
+
+    fs::write(out.join("days.rs"), code)
+}
+
+/// Every file under `dir`, sorted.
+fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut found = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        match path.is_dir() {
+            true => found.extend(files(&path)?),
+            false => found.push(path),
+        }
+    }
+    found.sort();
+    Ok(found)
+}
@@ -0,0 +1,125 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
+
+/// Wraps the system allocator, counting allocations made on each thread once
+/// [`enable`] has been called. Until then it only adds a flag check.
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn on_alloc(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // `try_with` because allocations can happen while thread locals are
+    // being torn down.
+    let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+    let _ = ALLOCATED.try_with(|n| n.set(n.get() + size as u64));
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + size as i64);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+fn on_dealloc(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let _ = LIVE.try_with(|live| live.set(live.get() - size as i64));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        on_alloc(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        on_alloc(layout.size());
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        on_dealloc(layout.size());
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        on_dealloc(layout.size());
+        on_alloc(new_size);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Allocation statistics for a stretch of work on one thread.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct MemStats {
+    /// Number of allocations, counting each reallocation as one.
+    pub allocations: u64,
+    /// Total bytes requested across all allocations.
+    pub allocated_bytes: u64,
+    /// Most bytes live at once, relative to the start.
+    pub peak_bytes: u64,
+}
+
+/// Runs `f`, counting the allocations it makes on the current thread.
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, MemStats) {
+    ALLOCATIONS.set(0);
+    ALLOCATED.set(0);
+    LIVE.set(0);
+    PEAK.set(0);
+    let res = f();
+    let stats = MemStats {
+        allocations: ALLOCATIONS.get(),
+        allocated_bytes: ALLOCATED.get(),
+        peak_bytes: PEAK.get().max(0) as u64,
+    };
+    (res, stats)
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut n = self.0 as f64;
+        let mut unit = 0;
+        while n >= 1024.0 && unit < UNITS.len() - 1 {
+            n /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{} {}", self.0, UNITS[0]),
+            _ => write!(f, "{:.1} {}", n, UNITS[unit]),
+        }
+    }
+}
+
+impl fmt::Display for MemStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.allocated_bytes),
+            Bytes(self.peak_bytes)
+        )
+    }
+}
@@ -0,0 +1,170 @@
+use std::{cmp::Ordering, convert::Infallible, fmt, str::FromStr};
+
+use serde::{Serialize, Serializer};
+
+/// The result of solving one part of a puzzle.
+///
+/// Integers are kept in [`Answer::Int`] whenever they fit, and only spill into
+/// [`Answer::BigInt`] otherwise; the two compare equal when they hold the same
+/// value. Strings that are exactly how an integer prints are converted to one,
+/// and text compares equal to the integer it spells out.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+    /// The part has no implementation yet.
+    Unsolved,
+}
+
+impl Answer {
+    const UNSOLVED: &str = "[unsolved]";
+
+    pub fn is_solved(&self) -> bool {
+        !matches!(self, Answer::Unsolved)
+    }
+
+    pub fn as_integer(&self) -> Option<i128> {
+        match *self {
+            Answer::Int(n) => Some(n as i128),
+            Answer::BigInt(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn from_i128(n: i128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+macro_rules! from_small_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n.into())
+            }
+        })*
+    };
+}
+from_small_int!(i8, i16, i32, i64, u8, u16, u32);
+
+macro_rules! from_large_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match i128::try_from(n) {
+                    Ok(n) => Answer::from_i128(n),
+                    Err(_) => Answer::Text(n.to_string()),
+                }
+            }
+        })*
+    };
+}
+from_large_int!(i128, u64, u128, usize, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        // Only when the number prints back the same, so `007` stays as it is.
+        match integer_text(&s) {
+            Some(n) => Answer::from_i128(n),
+            None => Answer::Text(s),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        s.to_owned().into()
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a.partial_cmp(b),
+            (Answer::Unsolved, Answer::Unsolved) => Some(Ordering::Equal),
+            (Answer::Text(a), b) => integer_text(a)?.partial_cmp(&b.as_integer()?),
+            (a, Answer::Text(b)) => a.as_integer()?.partial_cmp(&integer_text(b)?),
+            (a, b) => a.as_integer()?.partial_cmp(&b.as_integer()?),
+        }
+    }
+}
+
+/// The integer `s` is the printed form of, if any.
+fn integer_text(s: &str) -> Option<i128> {
+    s.parse().ok().filter(|n: &i128| n.to_string() == s)
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::BigInt(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+            Answer::Unsolved => Self::UNSOLVED.fmt(f),
+        }
+    }
+}
+
+/// Parses the [`Display`](fmt::Display) form back into an answer: integers
+/// become numeric answers, and anything else is kept as text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            Self::UNSOLVED => Answer::Unsolved,
+            _ => s.into(),
+        })
+    }
+}
+
+/// Numeric answers serialize as numbers, text as a string, and unsolved parts
+/// as `null`.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(n) => s.serialize_i64(*n),
+            Answer::BigInt(n) => s.serialize_i128(*n),
+            Answer::Text(t) => s.serialize_str(t),
+            Answer::Unsolved => s.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_strings_become_integers() {
+        assert!(matches!(Answer::from("42"), Answer::Int(42)));
+        assert!(matches!(Answer::from("-7".to_owned()), Answer::Int(-7)));
+        assert!(matches!(
+            Answer::from("170141183460469231731687303715884105727"),
+            Answer::BigInt(i128::MAX)
+        ));
+        assert!(matches!(Answer::from("abc"), Answer::Text(_)));
+        assert_eq!(Answer::from("007").to_string(), "007");
+        assert_eq!(Answer::from("+5").to_string(), "+5");
+    }
+
+    #[test]
+    fn text_equals_the_integer_it_spells() {
+        assert_eq!(Answer::Text("3".to_owned()), Answer::Int(3));
+        assert_eq!(Answer::Int(3), Answer::Text("3".to_owned()));
+        assert_eq!(Answer::Text("3".to_owned()), Answer::BigInt(3));
+        assert_ne!(Answer::Text("three".to_owned()), Answer::Int(3));
+        assert_ne!(Answer::Text("007".to_owned()), Answer::Int(7));
+        assert_eq!(Answer::from(3.to_string()), "3".parse::<Answer>().unwrap());
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::output::serialize_nanos;
+
+/// Timing statistics over repeated runs of a part. Durations serialize as
+/// nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Stats {
+    pub runs: usize,
+    #[serde(serialize_with = "serialize_nanos")]
+    pub min: Duration,
+    #[serde(serialize_with = "serialize_nanos")]
+    pub median: Duration,
+    #[serde(serialize_with = "serialize_nanos")]
+    pub mean: Duration,
+    #[serde(serialize_with = "serialize_nanos")]
+    pub p95: Duration,
+    #[serde(serialize_with = "serialize_nanos")]
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let runs = samples.len();
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / runs as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / runs as f64;
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            p95: samples[(runs * 95).div_ceil(100) - 1],
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2?}  median {:.2?}  mean {:.2?}  p95 {:.2?}  σ {:.2?}  ({} runs)",
+            self.min, self.median, self.mean, self.p95, self.std_dev, self.runs
+        )
+    }
+}
+
+/// Runs `f` `runs` times after a short warmup, returning the last result and
+/// the timings of the measured runs.
+pub fn bench<T>(runs: usize, mut f: impl FnMut() -> Result<T>) -> Result<(T, Stats)> {
+    let runs = runs.max(1);
+    for _ in 0..(runs / 10).max(1) {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(runs);
+    let mut last = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        last = Some(f()?);
+        samples.push(Instant::now().duration_since(start));
+    }
+
+    Ok((
+        last.expect("at least one run"),
+        Stats::from_samples(samples),
+    ))
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{Context as _, Result};
+use fxhash::FxHasher64;
+use serde::{Deserialize, Serialize};
+
+use aoc_2025::{
+    Context,
+    answer::Answer,
+    solution::{self, Part},
+};
+
+/// One cached answer. Only the key and answer are read back; the rest is for
+/// whoever looks at the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Hash of everything the answer depends on; see [`key`].
+    key: String,
+    day: u32,
+    part: u8,
+    input: String,
+    answer: String,
+}
+
+/// Answers from past runs, stored one JSON object per line, so that parts
+/// whose code and input haven't changed needn't be solved again.
+pub struct Cache {
+    path: PathBuf,
+    answers: Mutex<HashMap<String, Answer>>,
+    new: Mutex<Vec<Entry>>,
+    /// Whether the file's last line was cut short, so the next entry must
+    /// start a new one.
+    partial: Mutex<bool>,
+}
+
+/// Identifies a part's answer by hashing the day's module source, the rest of
+/// the crate's source, the input and the puzzle parameters.
+pub fn key(day: u32, part: Part, data: &[u8], ctx: &Context) -> Option<String> {
+    let mut hasher = FxHasher64::default();
+    let source = (solution::source(day)?, solution::shared_source_hash());
+    (day, part, source, data).hash(&mut hasher);
+    ctx.params().for_each(|param| param.hash(&mut hasher));
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+impl Cache {
+    /// Loads the cache at `path`; a missing file is an empty cache.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("cannot read cache {}", path.display()));
+            }
+        };
+        // The cache is only an optimisation, so a damaged line (say, one cut
+        // short by Ctrl-C) is skipped rather than stopping the run.
+        let answers = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .filter_map(|(i, l)| match serde_json::from_str::<Entry>(l) {
+                Ok(entry) => Some((entry.key, entry.answer.parse().ok()?)),
+                Err(e) => {
+                    eprintln!("warning: skipping {}:{}: {}", path.display(), i + 1, e);
+                    None
+                }
+            })
+            .collect();
+        Ok(Self {
+            path: path.to_owned(),
+            answers: Mutex::new(answers),
+            new: Mutex::default(),
+            partial: Mutex::new(!text.is_empty() && !text.ends_with('\n')),
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<Answer> {
+        self.answers.lock().unwrap().get(key).cloned()
+    }
+
+    /// Remembers an answer, to be written out by [`save`](Cache::save).
+    pub fn insert(&self, key: String, (day, part, input): (u32, Part, &str), answer: &Answer) {
+        let mut answers = self.answers.lock().unwrap();
+        if answers.get(&key) == Some(answer) {
+            return;
+        }
+        answers.insert(key.clone(), answer.clone());
+        self.new.lock().unwrap().push(Entry {
+            key,
+            day,
+            part: part.number(),
+            input: input.to_owned(),
+            answer: answer.to_string(),
+        });
+    }
+
+    /// Appends the answers inserted since the last save.
+    pub fn save(&self) -> Result<()> {
+        let new = std::mem::take(&mut *self.new.lock().unwrap());
+        if new.is_empty() {
+            return Ok(());
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("cannot open cache {}", self.path.display()))?;
+        if std::mem::take(&mut *self.partial.lock().unwrap()) {
+            writeln!(file)?;
+        }
+        for entry in &new {
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deletes the cache at `path`, returning whether there was one.
+pub fn clear(path: &Path) -> Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("cannot delete cache {}", path.display())),
+    }
+}
@@ -0,0 +1,103 @@
+//! Terminal colours, applied only when stdout can show them.
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use clap::ValueEnum;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Colour when writing to a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether output to `stream` should be decorated, with colour or with
+    /// progress bars.
+    pub fn enabled(self, stream: &impl IsTerminal) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && stream.is_terminal()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// Turns colour on or off for everything written with [`Paint`].
+pub fn init(choice: ColorChoice) {
+    ENABLED.store(choice.enabled(&io::stdout()), Ordering::Relaxed);
+}
+
+/// Whether output is being decorated, which also means it's fine to move
+/// the cursor around.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Wraps `text` in the SGR `code`, merging with any styling already at its
+/// start so that nested styles share one reset.
+fn paint(text: String, code: u8) -> String {
+    if !enabled() {
+        return text;
+    }
+    match text.strip_prefix("\x1b[") {
+        Some(rest) => format!("\x1b[{};{}", code, rest),
+        None => format!("\x1b[{}m{}\x1b[0m", code, text),
+    }
+}
+
+/// ANSI styling for strings, which does nothing unless colour is enabled.
+pub trait Paint: Sized {
+    fn paint(self, code: u8) -> String;
+
+    fn bold(self) -> String {
+        self.paint(1)
+    }
+
+    fn red(self) -> String {
+        self.paint(31)
+    }
+
+    fn green(self) -> String {
+        self.paint(32)
+    }
+
+    fn yellow(self) -> String {
+        self.paint(33)
+    }
+
+    fn blue(self) -> String {
+        self.paint(34)
+    }
+
+    fn magenta(self) -> String {
+        self.paint(35)
+    }
+
+    /// Bright black, i.e. grey.
+    fn b_black(self) -> String {
+        self.paint(90)
+    }
+}
+
+impl Paint for String {
+    fn paint(self, code: u8) -> String {
+        paint(self, code)
+    }
+}
+
+impl Paint for &str {
+    fn paint(self, code: u8) -> String {
+        paint(self.to_owned(), code)
+    }
+}
@@ -0,0 +1,110 @@
+use std::{collections::BTreeMap, fs, io, path::Path, str::FromStr};
+
+use anyhow::{Context as _, Result, anyhow, bail};
+
+use crate::{progress::Progress, solution::Part};
+
+/// What a solution knows about the input it is solving beyond the input
+/// itself: puzzle parameters that differ between the examples and the real
+/// input, such as how many steps to simulate.
+///
+/// Parameters come from a sidecar file next to the input, with the same name
+/// but a `.meta` extension (`test.meta` for `test.txt`), and can be
+/// overridden from the command line. Each non-empty line of the sidecar reads
+/// `<key> = <value>`; lines starting with `#` are comments. The `parts` key
+/// is special: it lists the parts an example is for (`parts = 2`), and the
+/// others are never run on it.
+///
+/// It also carries the [`Progress`] handle for the part being solved.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    params: BTreeMap<String, String>,
+    progress: Progress,
+}
+
+impl Context {
+    /// Loads the sidecar for the input at `input`, if there is one.
+    pub fn load(input: &Path) -> Result<Self> {
+        let path = input.with_extension("meta");
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("cannot read {}", path.display()));
+            }
+        };
+
+        let mut ctx = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                anyhow!(
+                    "{}:{}: expected '<key> = <value>', got '{}'",
+                    path.display(),
+                    i + 1,
+                    line
+                )
+            })?;
+            ctx.set(key.trim(), value.trim());
+        }
+        Ok(ctx)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.params.insert(key.to_owned(), value.to_owned());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    /// Every parameter, in key order.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Parses the parameter `key`, falling back to `default` if it isn't set.
+    pub fn param<T>(&self, key: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match self.get(key) {
+            Some(value) => value
+                .parse()
+                .with_context(|| format!("invalid value '{}' for parameter '{}'", value, key)),
+            None => Ok(default),
+        }
+    }
+
+    /// The parts this input is for, as listed by the `parts` parameter; all
+    /// of them if it isn't set.
+    pub fn parts(&self) -> Result<Vec<Part>> {
+        let Some(value) = self.get("parts") else {
+            return Ok(Part::ALL.to_vec());
+        };
+        value
+            .split(',')
+            .map(|p| match p.trim() {
+                "1" => Ok(Part::One),
+                "2" => Ok(Part::Two),
+                p => bail!("invalid part '{}' in parameter 'parts'", p),
+            })
+            .collect()
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// A copy of this context reporting progress through `progress`.
+    pub fn with_progress(&self, progress: Progress) -> Self {
+        Self {
+            params: self.params.clone(),
+            progress,
+        }
+    }
+}
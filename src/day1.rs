@@ -9,9 +9,11 @@ use nom::{
     sequence::pair,
 };
 
-use crate::Input;
+use crate::solution::solution;
 
-fn parse(s: &str) -> (i32, i32) {
+solution!(1, "Secret Entrance");
+
+fn parse_line(s: &str) -> (i32, i32) {
     let res: Result<(&str, (i32, i32)), nom::Err<()>> = all_consuming(pair(
         alt((value(-1, char('L')), value(1, char('R')))),
         map_res(recognize(digit1), str::parse),
@@ -21,13 +23,15 @@ fn parse(s: &str) -> (i32, i32) {
     res.unwrap().1
 }
 
-pub fn part1(input: Input) -> Result<String> {
+pub fn parse(input: impl BufRead) -> Result<Vec<(i32, i32)>> {
+    input.lines().map(|line| Ok(parse_line(&line?))).collect()
+}
+
+pub fn part1(rotations: &[(i32, i32)]) -> Result<String> {
     let mut pos: i32 = 50;
     let mut total = 0;
 
-    for line in input.lines() {
-        let (dir, n) = parse(&line.unwrap());
-
+    for &(dir, n) in rotations {
         pos += dir * n;
         pos = pos.rem_euclid(100);
         if pos == 0 {
@@ -38,13 +42,11 @@ pub fn part1(input: Input) -> Result<String> {
     Ok(total.to_string())
 }
 
-pub fn part2(input: Input) -> Result<String> {
+pub fn part2(rotations: &[(i32, i32)]) -> Result<String> {
     let mut pos: i32 = 50;
     let mut total = 0;
 
-    for line in input.lines() {
-        let (dir, n) = parse(&line.unwrap());
-
+    for &(dir, n) in rotations {
         pos = pos.rem_euclid(100);
         if pos == 0 && dir == -1 {
             pos = 100;
@@ -1,8 +1,7 @@
 use std::{io::BufRead, str::FromStr};
 
 use anyhow::{Result, anyhow};
-// use fxhash::FxHashSet;
-// use indicatif::ProgressBar;
+use fxhash::FxHashSet;
 use nom::{
     Parser,
     character::{
@@ -16,9 +15,11 @@ use nom::{
     sequence::delimited,
 };
 
-use crate::Input;
+use crate::{answer::Answer, context::Context, solution::solution};
 
-fn parse<T: FromStr>(s: &str) -> (Vec<bool>, Vec<Vec<T>>, Vec<T>) {
+solution!(10, "Factory", variants { 2: [dfs = part2_dfs] });
+
+fn parse_line<T: FromStr>(s: &str) -> (Vec<bool>, Vec<Vec<T>>, Vec<T>) {
     fn num_parser<'a, T: FromStr>() -> impl Parser<&'a str, Output = T, Error = (&'a str, ErrorKind)>
     {
         map_res(recognize(complete::digit1), str::parse)
@@ -56,6 +57,27 @@ fn parse<T: FromStr>(s: &str) -> (Vec<bool>, Vec<Vec<T>>, Vec<T>) {
     res
 }
 
+#[derive(Debug, Clone)]
+pub struct Machine {
+    pub lights: Vec<bool>,
+    pub buttons: Vec<Vec<usize>>,
+    pub joltage: Vec<usize>,
+}
+
+pub fn parse(input: impl BufRead) -> Result<Vec<Machine>> {
+    input
+        .lines()
+        .map(|line| {
+            let (lights, buttons, joltage) = parse_line(line?.as_ref());
+            Ok(Machine {
+                lights,
+                buttons,
+                joltage,
+            })
+        })
+        .collect()
+}
+
 #[inline]
 fn part1_press(lights: &mut [bool], button: &[usize]) {
     for &x in button {
@@ -90,101 +112,101 @@ fn part1_search(target_lights: &[bool], buttons: &[Vec<usize>], depth: usize) ->
     None
 }
 
-pub fn part1(input: Input) -> Result<usize> {
+pub fn part1(machines: &[Machine]) -> Result<usize> {
+    let mut total = 0;
+    for (i, machine) in machines.iter().enumerate() {
+        total += (0..machine.buttons.len())
+            .flat_map(|depth| part1_search(&machine.lights, &machine.buttons, depth))
+            .next()
+            .ok_or_else(|| anyhow!("no solution found for machine {}", i + 1))?;
+    }
+
+    Ok(total)
+}
+
+#[inline]
+fn part2_press(joltage: &mut [usize], button: &[usize]) {
+    for &x in button {
+        joltage[x] += 1
+    }
+}
+
+#[inline]
+fn part2_unpress(joltage: &mut [usize], button: &[usize]) {
+    for &x in button {
+        joltage[x] -= 1
+    }
+}
+
+fn part2_search(target_joltage: &[usize], buttons: &[Vec<usize>], depth: usize) -> Option<usize> {
+    let mut stack: Vec<usize> = vec![0];
+    let mut current_joltage = vec![0; target_joltage.len()];
+    let mut map: FxHashSet<Vec<usize>> = FxHashSet::default();
+
+    while let Some(&last) = stack.last() {
+        if last == buttons.len() {
+            stack.pop();
+            if let Some(last) = stack.last_mut() {
+                part2_unpress(&mut current_joltage, &buttons[*last]);
+                map.insert(current_joltage.clone());
+                *last += 1;
+            }
+            continue;
+        }
+        part2_press(&mut current_joltage, &buttons[last]);
+        if current_joltage == target_joltage {
+            return Some(stack.len());
+        } else if stack.len()
+            + target_joltage
+                .iter()
+                .zip(current_joltage.iter())
+                .map(|(&a, &b)| a.saturating_sub(b))
+                .min()
+                .unwrap()
+            <= depth
+            && target_joltage
+                .iter()
+                .zip(current_joltage.iter())
+                .all(|(a, b)| a >= b)
+            && !map.contains(&current_joltage)
+            && target_joltage
+                .iter()
+                .zip(current_joltage.iter())
+                .enumerate()
+                .filter(|&(_, (a, b))| a > b)
+                .all(|(i, _)| buttons[last..].iter().any(|button| button.contains(&i)))
+        {
+            stack.push(last);
+        } else {
+            map.insert(current_joltage.clone());
+            part2_unpress(&mut current_joltage, &buttons[last]);
+            *stack.last_mut().unwrap() += 1;
+        }
+    }
+
+    None
+}
+
+/// Iterative deepening over button presses. Correct, but far too slow for the
+/// real input.
+pub fn part2_dfs(machines: &[Machine], ctx: &Context) -> Result<usize> {
     let mut total = 0;
-    for line in input.lines() {
-        let line = line?;
-        let (lights, buttons, _) = parse::<usize>(line.as_ref());
-        total += (0..buttons.len())
-            .flat_map(|i| part1_search(&lights, &buttons, i))
+    let progress = ctx.progress();
+    progress.set_total(machines.len() as u64);
+    for (i, machine) in machines.iter().enumerate() {
+        let mut buttons = machine.buttons.clone();
+        buttons.sort_by_key(|x| -(x.len() as isize));
+        let joltage = &machine.joltage;
+        total += (*joltage.iter().min().unwrap()..)
+            .flat_map(|depth| part2_search(joltage, &buttons, depth))
             .next()
-            .ok_or_else(|| anyhow!("no solution found for line: {}", line))?;
+            .ok_or_else(|| anyhow!("no solution found for machine {}", i + 1))?;
+        progress.inc(1);
     }
 
     Ok(total)
 }
 
-// #[inline]
-// fn part2_press(joltage: &mut [usize], button: &[usize]) {
-//     for &x in button {
-//         joltage[x] += 1
-//     }
-// }
-
-// #[inline]
-// fn part2_unpress(joltage: &mut [usize], button: &[usize]) {
-//     for &x in button {
-//         joltage[x] -= 1
-//     }
-// }
-
-// fn part2_search(target_joltage: &[usize], buttons: &[Vec<usize>], depth: usize) -> Option<usize> {
-//     let mut stack: Vec<usize> = vec![0];
-//     let mut current_joltage = vec![0; target_joltage.len()];
-//     let mut map: FxHashSet<Vec<usize>> = FxHashSet::default();
-
-//     while let Some(&last) = stack.last() {
-//         if last == buttons.len() {
-//             stack.pop();
-//             if let Some(last) = stack.last_mut() {
-//                 part2_unpress(&mut current_joltage, &buttons[*last]);
-//                 map.insert(current_joltage.clone());
-//                 *last += 1;
-//             }
-//             continue;
-//         }
-//         part2_press(&mut current_joltage, &buttons[last]);
-//         if current_joltage == target_joltage {
-//             return Some(stack.len());
-//         } else if stack.len()
-//             + target_joltage
-//                 .iter()
-//                 .zip(current_joltage.iter())
-//                 .map(|(&a, &b)| a.saturating_sub(b))
-//                 .min()
-//                 .unwrap()
-//             <= depth
-//             && target_joltage
-//                 .iter()
-//                 .zip(current_joltage.iter())
-//                 .all(|(a, b)| a >= b)
-//             && !map.contains(&current_joltage)
-//             && target_joltage
-//                 .iter()
-//                 .zip(current_joltage.iter())
-//                 .enumerate()
-//                 .filter(|&(_, (a, b))| a > b)
-//                 .all(|(i, _)| buttons[last..].iter().any(|button| button.contains(&i)))
-//         {
-//             stack.push(last);
-//         } else {
-//             map.insert(current_joltage.clone());
-//             part2_unpress(&mut current_joltage, &buttons[last]);
-//             *stack.last_mut().unwrap() += 1;
-//         }
-//     }
-
-//     None
-// }
-
-// pub fn part2(input: Input) -> Result<usize> {
-//     let mut total = 0;
-//     let bar = ProgressBar::new(154);
-//     for line in input.lines() {
-//         let line = line?;
-//         let (_, mut buttons, joltage) = parse::<usize>(line.as_ref());
-//         buttons.sort_by_key(|x| -(x.len() as isize));
-//         total += (*joltage.iter().min().unwrap()..)
-//             .flat_map(|i| part2_search(&joltage, &buttons, i))
-//             .next()
-//             .ok_or_else(|| anyhow!("no solution found for line: {}", line))?;
-//         bar.inc(1);
-//     }
-//     bar.finish_and_clear();
-
-//     Ok(total)
-// }
-
-pub fn part2(_input: Input) -> Result<&'static str> {
-    Ok("[todo]")
+pub fn part2(_machines: &[Machine]) -> Result<Answer> {
+    Ok(Answer::Unsolved)
 }
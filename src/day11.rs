@@ -1,6 +1,6 @@
-use std::{cell::Cell, collections::HashMap, io::BufRead, ops::Add};
+use std::{collections::HashMap, io::BufRead, ops::Add};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use fxhash::FxHashMap;
 use nom::{
     Parser,
@@ -10,24 +10,26 @@ use nom::{
     multi::separated_list0,
 };
 
-use crate::Input;
+use crate::{context::Context, solution::solution};
+
+solution!(11, "Reactor");
 
 #[derive(Debug, Clone, Copy)]
-enum Output {
+pub enum Output {
     Dev(usize),
     Out,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
-struct Counts {
-    neither: usize,
-    dac: usize,
-    fft: usize,
-    both: usize,
+pub struct Counts {
+    pub neither: usize,
+    pub dac: usize,
+    pub fft: usize,
+    pub both: usize,
 }
 
 impl Counts {
-    fn total(&self) -> usize {
+    pub fn total(&self) -> usize {
         self.neither + self.dac + self.fft + self.both
     }
 
@@ -61,10 +63,9 @@ impl From<[usize; 4]> for Counts {
 }
 
 #[derive(Debug)]
-struct Device {
-    _name: String,
-    con: Vec<Output>,
-    n: Cell<Option<Counts>>,
+pub struct Device {
+    pub name: String,
+    pub con: Vec<Output>,
 }
 
 fn parse_line(s: String) -> (String, Vec<String>) {
@@ -78,34 +79,38 @@ fn parse_line(s: String) -> (String, Vec<String>) {
     (dev.to_owned(), con.into_iter().map(str::to_owned).collect())
 }
 
-fn parse(input: Input, start: &str) -> (Vec<Device>, usize) {
+pub fn parse(input: impl BufRead) -> Result<Vec<Device>> {
     let mut map: FxHashMap<String, usize> = HashMap::default();
     let lines: Vec<_> = input.lines().map(Result::unwrap).map(parse_line).collect();
     for (i, (dev, _)) in lines.iter().enumerate() {
         map.insert(dev.to_owned(), i);
     }
-    (
-        lines
-            .into_iter()
-            .map(|(name, con)| Device {
-                _name: name,
-                con: con
-                    .into_iter()
-                    .map(|s| match s.as_ref() {
-                        "out" => Output::Out,
-                        _ => Output::Dev(*map.get(&s).unwrap()),
-                    })
-                    .collect(),
-                n: None.into(),
-            })
-            .collect(),
-        *map.get(start).unwrap(),
-    )
+    Ok(lines
+        .into_iter()
+        .map(|(name, con)| Device {
+            name,
+            con: con
+                .into_iter()
+                .map(|s| match s.as_ref() {
+                    "out" => Output::Out,
+                    _ => Output::Dev(*map.get(&s).unwrap()),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+pub fn find(devs: &[Device], name: &str) -> Option<usize> {
+    devs.iter().position(|d| d.name == name)
 }
 
-fn search(devs: &[Device], start: usize) -> Counts {
+pub fn search(devs: &[Device], start: usize) -> Counts {
+    search_memo(devs, start, &mut vec![None; devs.len()])
+}
+
+fn search_memo(devs: &[Device], start: usize, memo: &mut [Option<Counts>]) -> Counts {
     let dev = &devs[start];
-    if let Some(n) = dev.n.get() {
+    if let Some(n) = memo[start] {
         return n;
     }
 
@@ -113,7 +118,7 @@ fn search(devs: &[Device], start: usize) -> Counts {
         .con
         .iter()
         .map(|&o| match o {
-            Output::Dev(x) => search(devs, x),
+            Output::Dev(x) => search_memo(devs, x, memo),
             Output::Out => Counts {
                 neither: 1,
                 dac: 0,
@@ -124,27 +129,33 @@ fn search(devs: &[Device], start: usize) -> Counts {
         .reduce(Add::add)
         .unwrap();
 
-    if dev._name == "dac" {
+    if dev.name == "dac" {
         res.dac += res.neither;
         res.both += res.fft;
         res.neither = 0;
         res.fft = 0;
-    } else if dev._name == "fft" {
+    } else if dev.name == "fft" {
         res.fft += res.neither;
         res.both += res.dac;
         res.neither = 0;
         res.dac = 0;
     }
-    dev.n.set(Some(res));
+    memo[start] = Some(res);
     res
 }
 
-pub fn part1(input: Input) -> Result<usize> {
-    let (devs, start) = parse(input, "you");
-    Ok(search(&devs, start).total())
+/// Finds the device to start from, named by the `<part>.start` parameter.
+fn start(devs: &[Device], ctx: &Context, part: &str, default: &str) -> Result<usize> {
+    let name = ctx.get(&format!("{}.start", part)).unwrap_or(default);
+    find(devs, name).ok_or_else(|| anyhow!("no device named '{}'", name))
+}
+
+pub fn part1(devs: &[Device], ctx: &Context) -> Result<usize> {
+    let start = start(devs, ctx, "part1", "you")?;
+    Ok(search(devs, start).total())
 }
 
-pub fn part2(input: Input) -> Result<usize> {
-    let (devs, start) = parse(input, "svr");
-    Ok(search(&devs, start).both)
+pub fn part2(devs: &[Device], ctx: &Context) -> Result<usize> {
+    let start = start(devs, ctx, "part2", "svr")?;
+    Ok(search(devs, start).both)
 }
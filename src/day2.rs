@@ -1,4 +1,7 @@
-use std::{io::read_to_string, str::FromStr};
+use std::{
+    io::{BufRead, read_to_string},
+    str::FromStr,
+};
 
 use anyhow::Result;
 use nom::{
@@ -9,9 +12,11 @@ use nom::{
     multi::separated_list0,
 };
 
-use crate::Input;
+use crate::solution::solution;
+
+solution!(2, "Gift Shop");
 
-fn parse<T: FromStr>(s: &str) -> Vec<(T, T)> {
+fn parse_ranges<T: FromStr>(s: &str) -> Vec<(T, T)> {
     fn num_parser<'a, T: FromStr>() -> impl Parser<&'a str, Output = T, Error = (&'a str, ErrorKind)>
     {
         map_res(recognize(digit1), str::parse)
@@ -26,9 +31,11 @@ fn parse<T: FromStr>(s: &str) -> Vec<(T, T)> {
     vec
 }
 
-fn run(input: Input, only_2: bool) -> u64 {
-    let ids = parse::<String>(read_to_string(input).unwrap().trim());
+pub fn parse(input: impl BufRead) -> Result<Vec<(String, String)>> {
+    Ok(parse_ranges(read_to_string(input)?.trim()))
+}
 
+fn run(ids: &[(String, String)], only_2: bool) -> u64 {
     ids.iter()
         .map(|(a, b)| [a.as_ref(), b.as_ref()])
         .flat_map(|[a, b]: [&str; 2]| {
@@ -88,10 +95,10 @@ fn run(input: Input, only_2: bool) -> u64 {
         .sum()
 }
 
-pub fn part1(input: Input) -> Result<u64> {
-    Ok(run(input, true))
+pub fn part1(ids: &[(String, String)]) -> Result<u64> {
+    Ok(run(ids, true))
 }
 
-pub fn part2(input: Input) -> Result<u64> {
-    Ok(run(input, false))
+pub fn part2(ids: &[(String, String)]) -> Result<u64> {
+    Ok(run(ids, false))
 }
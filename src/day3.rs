@@ -2,18 +2,23 @@ use std::io::BufRead;
 
 use anyhow::Result;
 
-use crate::Input;
-
-fn parse(input: Input) -> impl Iterator<Item = Vec<u64>> {
-    input.lines().map(|x| {
-        x.unwrap()
-            .chars()
-            .map(|c| c.to_digit(10).expect("failed to parse digit") as u64)
-            .collect()
-    })
+use crate::solution::solution;
+
+solution!(3, "Lobby");
+
+pub fn parse(input: impl BufRead) -> Result<Vec<Vec<u64>>> {
+    input
+        .lines()
+        .map(|x| {
+            Ok(x?
+                .chars()
+                .map(|c| c.to_digit(10).expect("failed to parse digit") as u64)
+                .collect())
+        })
+        .collect()
 }
 
-fn process_bank<const N: usize>(bank: Vec<u64>) -> u64 {
+pub fn process_bank<const N: usize>(bank: &[u64]) -> u64 {
     let mut digits = [0; N];
     let mut start = 0;
 
@@ -30,10 +35,10 @@ fn process_bank<const N: usize>(bank: Vec<u64>) -> u64 {
     digits.iter().fold(0, |acc, x| acc * 10 + x)
 }
 
-pub fn part1(input: Input) -> Result<u64> {
-    Ok(parse(input).map(process_bank::<2>).sum())
+pub fn part1(banks: &[Vec<u64>]) -> Result<u64> {
+    Ok(banks.iter().map(|b| process_bank::<2>(b)).sum())
 }
 
-pub fn part2(input: Input) -> Result<u64> {
-    Ok(parse(input).map(process_bank::<12>).sum())
+pub fn part2(banks: &[Vec<u64>]) -> Result<u64> {
+    Ok(banks.iter().map(|b| process_bank::<12>(b)).sum())
 }
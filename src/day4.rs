@@ -2,15 +2,16 @@ use std::io::BufRead;
 
 use anyhow::Result;
 
-use crate::Input;
+use crate::solution::solution;
 
-pub fn part1(input: Input) -> Result<usize> {
-    let grid = PaperGrid::from(input);
+solution!(4, "Printing Department");
+
+pub fn part1(grid: &PaperGrid) -> Result<usize> {
     Ok(grid.count() - grid.remove_rolls().count())
 }
 
-pub fn part2(input: Input) -> Result<usize> {
-    let mut grid = PaperGrid::from(input);
+pub fn part2(grid: &PaperGrid) -> Result<usize> {
+    let mut grid = grid.clone();
     let nb_rolls = grid.count();
     loop {
         let next = grid.remove_rolls();
@@ -24,7 +25,7 @@ pub fn part2(input: Input) -> Result<usize> {
 
 type Inner = u8;
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct PaperGrid {
+pub struct PaperGrid {
     data: Vec<Inner>,
     width: usize,
     height: usize,
@@ -41,7 +42,7 @@ impl PaperGrid {
             self.data[x as usize + y as usize * self.width]
         }
     }
-    fn remove_rolls(&self) -> Self {
+    pub fn remove_rolls(&self) -> Self {
         Self {
             data: self
                 .rows()
@@ -70,34 +71,32 @@ impl PaperGrid {
             height: self.height,
         }
     }
-    fn count(&self) -> usize {
+    pub fn count(&self) -> usize {
         self.rows()
             .flat_map(|r| r.iter().filter(|&&x| x != 0))
             .count()
     }
 }
 
-impl From<Input> for PaperGrid {
-    fn from(input: Input) -> Self {
-        let mut width = 0;
-        let vec: Vec<_> = input
-            .lines()
-            .flat_map(|l| {
-                let l = l.unwrap();
-                width = l.len();
-                l.chars()
-                    .map(|c| match c {
-                        '.' => 0,
-                        '@' => 1,
-                        x => panic!("unexpected char {}", x),
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-        Self {
-            height: vec.len() / width,
-            data: vec,
-            width,
-        }
-    }
+pub fn parse(input: impl BufRead) -> Result<PaperGrid> {
+    let mut width = 0;
+    let vec: Vec<_> = input
+        .lines()
+        .flat_map(|l| {
+            let l = l.unwrap();
+            width = l.len();
+            l.chars()
+                .map(|c| match c {
+                    '.' => 0,
+                    '@' => 1,
+                    x => panic!("unexpected char {}", x),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    Ok(PaperGrid {
+        height: vec.len() / width,
+        data: vec,
+        width,
+    })
 }
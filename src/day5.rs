@@ -8,9 +8,11 @@ use nom::{
     error::ErrorKind,
 };
 
-use crate::Input;
+use crate::solution::solution;
 
-fn parse<T: FromStr>(s: &str) -> (T, T) {
+solution!(5, "Cafeteria");
+
+fn parse_range<T: FromStr>(s: &str) -> (T, T) {
     fn num_parser<'a, T: FromStr>() -> impl Parser<&'a str, Output = T, Error = (&'a str, ErrorKind)>
     {
         map_res(recognize(digit1), str::parse)
@@ -20,10 +22,10 @@ fn parse<T: FromStr>(s: &str) -> (T, T) {
     (a, b)
 }
 
-fn get_ranges(lines: &mut impl Iterator<Item = String>) -> Vec<(u64, u64)> {
+pub fn get_ranges(lines: &mut impl Iterator<Item = String>) -> Vec<(u64, u64)> {
     let mut ranges_raw: Vec<(u64, u64)> = vec![];
     for line in lines.by_ref().take_while(|l| !l.is_empty()) {
-        ranges_raw.push(parse(&line));
+        ranges_raw.push(parse_range(&line));
     }
     ranges_raw.sort_by_key(|x| x.0);
 
@@ -40,13 +42,26 @@ fn get_ranges(lines: &mut impl Iterator<Item = String>) -> Vec<(u64, u64)> {
     ranges
 }
 
-pub fn part1(input: Input) -> Result<usize> {
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    /// Fresh ingredient ID ranges, sorted and merged.
+    pub ranges: Vec<(u64, u64)>,
+    pub ids: Vec<u64>,
+}
+
+pub fn parse(input: impl BufRead) -> Result<Inventory> {
     let mut lines = input.lines().map(|x| x.unwrap());
     let ranges = get_ranges(&mut lines);
+    let ids = lines.map(|l| l.parse()).collect::<Result<_, _>>()?;
+    Ok(Inventory { ranges, ids })
+}
 
-    let count = lines
-        .map(|l| l.parse().unwrap())
-        .filter(|&n| match ranges.get(ranges.partition_point(|x| x.1 < n)) {
+pub fn part1(inventory: &Inventory) -> Result<usize> {
+    let Inventory { ranges, ids } = inventory;
+
+    let count = ids
+        .iter()
+        .filter(|&&n| match ranges.get(ranges.partition_point(|x| x.1 < n)) {
             Some(&(a, _)) => a <= n,
             None => false,
         })
@@ -55,9 +70,6 @@ pub fn part1(input: Input) -> Result<usize> {
     Ok(count)
 }
 
-pub fn part2(input: Input) -> Result<u64> {
-    let mut lines = input.lines().map(|x| x.unwrap());
-    let ranges = get_ranges(&mut lines);
-
-    Ok(ranges.into_iter().map(|(a, b)| b - a + 1).sum())
+pub fn part2(inventory: &Inventory) -> Result<u64> {
+    Ok(inventory.ranges.iter().map(|(a, b)| b - a + 1).sum())
 }
@@ -2,10 +2,15 @@ use std::{io::BufRead, iter::from_fn};
 
 use anyhow::Result;
 
-use crate::Input;
+use crate::solution::solution;
 
-pub fn part1(input: Input) -> Result<u64> {
-    let lines: Vec<_> = input.lines().map(|x| x.unwrap()).collect();
+solution!(6, "Trash Compactor");
+
+pub fn parse(input: impl BufRead) -> Result<Vec<String>> {
+    Ok(input.lines().collect::<Result<_, _>>()?)
+}
+
+pub fn part1(lines: &[String]) -> Result<u64> {
     let mut iter_iter = lines.iter().map(|l| l.split_whitespace());
     let mut num_iters: Vec<_> = iter_iter.by_ref().take(lines.len() - 1).collect();
     let ops = iter_iter.next().unwrap();
@@ -28,8 +33,7 @@ pub fn part1(input: Input) -> Result<u64> {
         .sum())
 }
 
-pub fn part2(input: Input) -> Result<u64> {
-    let lines: Vec<_> = input.lines().map(|x| x.unwrap()).collect();
+pub fn part2(lines: &[String]) -> Result<u64> {
     let mut num_iters: Vec<_> = lines[0..lines.len() - 1]
         .iter()
         .map(|l| l.chars())
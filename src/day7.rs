@@ -2,11 +2,21 @@ use std::{io::BufRead, mem};
 
 use anyhow::{Result, anyhow, bail};
 
-use crate::Input;
+use crate::solution::solution;
 
-fn run(input: Input) -> Result<(usize, usize)> {
+solution!(7, "Laboratories", solve_both);
+
+/// The tachyon manifold: beam counts entering the top row, and the splitter
+/// positions of each row below it.
+#[derive(Debug, Clone)]
+pub struct Manifold {
+    start: Vec<usize>,
+    rows: Vec<Vec<bool>>,
+}
+
+pub fn parse(input: impl BufRead) -> Result<Manifold> {
     let mut lines = input.lines();
-    let mut upper = lines
+    let start = lines
         .next()
         .ok_or(anyhow!("input is empty"))??
         .chars()
@@ -18,20 +28,38 @@ fn run(input: Input) -> Result<(usize, usize)> {
             })
         })
         .collect::<Result<Vec<_>>>()?;
+    let rows = lines
+        .map(|line| {
+            line?
+                .chars()
+                .map(|c| {
+                    Ok(match c {
+                        '.' => false,
+                        '^' => true,
+                        _ => bail!("invalid char: {}", c),
+                    })
+                })
+                .collect()
+        })
+        .collect::<Result<_>>()?;
+    Ok(Manifold { start, rows })
+}
+
+/// Returns the number of splits and the number of timelines.
+pub fn run(manifold: &Manifold) -> (usize, usize) {
+    let mut upper = manifold.start.clone();
     let mut lower = vec![0; upper.len()];
     let mut splits = 0;
 
-    for line in lines {
-        for (i, c) in line?.chars().enumerate() {
+    for row in &manifold.rows {
+        for (i, &splitter) in row.iter().enumerate() {
             if upper[i] != 0 {
-                match c {
-                    '.' => lower[i] += upper[i],
-                    '^' => {
-                        lower[i - 1] += upper[i];
-                        lower[i + 1] += upper[i];
-                        splits += 1;
-                    }
-                    _ => bail!("invalid char: {}", c),
+                if splitter {
+                    lower[i - 1] += upper[i];
+                    lower[i + 1] += upper[i];
+                    splits += 1;
+                } else {
+                    lower[i] += upper[i];
                 }
             }
         }
@@ -40,13 +68,17 @@ fn run(input: Input) -> Result<(usize, usize)> {
         lower.fill(0);
     }
 
-    Ok((splits, upper.iter().sum()))
+    (splits, upper.iter().sum())
+}
+
+pub fn part1(manifold: &Manifold) -> Result<usize> {
+    Ok(run(manifold).0)
 }
 
-pub fn part1(input: Input) -> Result<usize> {
-    run(input).map(|(a, _)| a)
+pub fn part2(manifold: &Manifold) -> Result<usize> {
+    Ok(run(manifold).1)
 }
 
-pub fn part2(input: Input) -> Result<usize> {
-    run(input).map(|(_, b)| b)
+pub fn solve_both(manifold: &Manifold) -> Result<(usize, usize)> {
+    Ok(run(manifold))
 }
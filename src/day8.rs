@@ -2,21 +2,22 @@ use std::{cell::Cell, io::BufRead, ops::Sub};
 
 use anyhow::{Result, anyhow, bail};
 use fxhash::FxHashMap;
-use indicatif::ProgressBar;
 use seq_macro::seq;
 
-use crate::Input;
+use crate::{context::Context, solution::solution};
+
+solution!(8, "Playground", solve_both);
 
 const BOXEL_SIDE: usize = 10_000;
 const N_BOXELS: usize = 10;
 const BUF_SIZE: usize = N_BOXELS * N_BOXELS * N_BOXELS;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
-struct Pos(i64, i64, i64);
+pub struct Pos(pub i64, pub i64, pub i64);
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
-struct JunctionBox {
-    pos: Pos,
+pub struct JunctionBox {
+    pub pos: Pos,
     connections: Vec<Pos>,
     closest: Cell<Option<Pos>>,
 }
@@ -111,39 +112,35 @@ fn union_find(grid: &Grid) -> Vec<usize> {
     res
 }
 
-#[derive(Debug)]
-struct Grid {
+#[derive(Debug, Clone)]
+pub struct Grid {
     data: Vec<Vec<JunctionBox>>,
 }
 
-impl TryFrom<Input> for Grid {
-    type Error = anyhow::Error;
-
-    fn try_from(input: Input) -> std::result::Result<Self, Self::Error> {
-        let mut data: Vec<Vec<_>> = vec![Vec::new(); BUF_SIZE];
-        for line in input.lines() {
-            let line = line?;
-            let mut iter = line.split(",");
-            macro_rules! next {
-                () => {
-                    iter.next()
-                        .ok_or_else(|| anyhow!("not enough fields on line '{}'", line))?
-                        .parse()?
-                };
-            }
-            let pos: Pos = Pos(next!(), next!(), next!());
-            let inx = Self::boxel_index(pos);
-            data[inx].push(JunctionBox {
-                pos,
-                ..Default::default()
-            });
+pub fn parse(input: impl BufRead) -> Result<Grid> {
+    let mut data: Vec<Vec<_>> = vec![Vec::new(); BUF_SIZE];
+    for line in input.lines() {
+        let line = line?;
+        let mut iter = line.split(",");
+        macro_rules! next {
+            () => {
+                iter.next()
+                    .ok_or_else(|| anyhow!("not enough fields on line '{}'", line))?
+                    .parse()?
+            };
         }
-        Ok(Grid { data })
+        let pos: Pos = Pos(next!(), next!(), next!());
+        let inx = Grid::boxel_index(pos);
+        data[inx].push(JunctionBox {
+            pos,
+            ..Default::default()
+        });
     }
+    Ok(Grid { data })
 }
 
 impl Grid {
-    fn iter(&self) -> impl Iterator<Item = &JunctionBox> {
+    pub fn iter(&self) -> impl Iterator<Item = &JunctionBox> {
         self.data.iter().flat_map(|b| b.iter())
     }
 
@@ -205,7 +202,7 @@ impl Grid {
             .unwrap_or((Pos::default(), i64::MAX))
     }
 
-    fn closest(&self, jb: &JunctionBox) -> (Pos, i64) {
+    pub fn closest(&self, jb: &JunctionBox) -> (Pos, i64) {
         if let Some(closest) = jb.closest.get() {
             return (closest, jb.pos - closest);
         }
@@ -237,71 +234,105 @@ impl Grid {
     }
 }
 
-pub fn part1(input: Input) -> Result<usize> {
-    let mut grid = Grid::try_from(input)?;
+/// Connects the closest pair of boxes that aren't already connected, returning
+/// their positions.
+fn connect_closest(grid: &mut Grid) -> Result<(Pos, Pos)> {
+    let (pos, (closest, _)) = grid
+        .iter()
+        .map(|jb| (jb.pos, grid.closest(jb)))
+        .min_by_key(|(_, (_, dist))| *dist)
+        .ok_or_else(|| anyhow!("no connections found!"))?;
+
+    if let Some(e) = grid.get_mut(closest) {
+        e.closest.set(None);
+        e.connections.push(pos);
+        let other = grid.get_mut(pos).unwrap();
+        other.closest.set(None);
+        other.connections.push(closest);
+        Ok((pos, closest))
+    } else {
+        bail!("no more connections");
+    }
+}
 
-    let n = match grid.iter().count() {
-        20 => 10,
-        _ => 1000,
-    };
+/// The number of connections to make for part 1; the example uses fewer.
+fn part1_connections(ctx: &Context) -> Result<usize> {
+    ctx.param("connections", 1000)
+}
 
-    for _ in 0..n {
-        let (pos, (closest, _)) = grid
-            .iter()
-            .map(|jb| (jb.pos, grid.closest(jb)))
-            .min_by_key(|(_, (_, dist))| *dist)
-            .ok_or_else(|| anyhow!("no connections found!"))?;
-
-        if let Some(e) = grid.get_mut(closest) {
-            e.closest.set(None);
-            e.connections.push(pos);
-            let other = grid.get_mut(pos).unwrap();
-            other.closest.set(None);
-            other.connections.push(closest);
-        } else {
-            bail!("no more connections");
-        }
+fn largest_circuits(grid: &Grid) -> usize {
+    union_find(grid).iter().take(3).product()
+}
+
+pub fn part1(grid: &Grid, ctx: &Context) -> Result<usize> {
+    let mut grid = grid.clone();
+
+    for _ in 0..part1_connections(ctx)? {
+        connect_closest(&mut grid)?;
     }
 
-    let circuit_sizes = union_find(&grid);
-    Ok(circuit_sizes.iter().take(3).product())
+    Ok(largest_circuits(&grid))
 }
 
-pub fn part2(input: Input) -> Result<i64> {
-    let mut grid = Grid::try_from(input)?;
+pub fn part2(grid: &Grid, ctx: &Context) -> Result<i64> {
+    let mut grid = grid.clone();
     let nodes = uf_init(&grid);
 
     let n = grid.iter().count();
-    let bar = ProgressBar::new(n as u64);
+    let progress = ctx.progress();
+    progress.set_total(n as u64);
 
     let mut max_size = 0;
 
     let res = loop {
-        bar.set_position(max_size as u64);
-        let (pos, (closest, _)) = grid
-            .iter()
-            .map(|jb| (jb.pos, grid.closest(jb)))
-            .min_by_key(|(_, (_, dist))| *dist)
-            .ok_or_else(|| anyhow!("no connections found!"))?;
-
-        if let Some(e) = grid.get_mut(closest) {
-            e.closest.set(None);
-            e.connections.push(pos);
-            let other = grid.get_mut(pos).unwrap();
-            other.closest.set(None);
-            other.connections.push(closest);
+        progress.set_position(max_size as u64);
+        let (pos, closest) = connect_closest(&mut grid)?;
+
+        nodes[&pos].union(&nodes[&closest]);
+        max_size = max_size.max(nodes[&pos].root().size.get());
+        if max_size == n {
+            break pos.0 * closest.0;
+        }
+    };
 
+    Ok(res)
+}
+
+/// Makes connections once for both parts, taking the part 1 answer on the way
+/// to part 2.
+pub fn solve_both(grid: &Grid, ctx: &Context) -> Result<(usize, i64)> {
+    let mut grid = grid.clone();
+    let nodes = uf_init(&grid);
+
+    let n = grid.iter().count();
+    let target = part1_connections(ctx)?;
+    let progress = ctx.progress();
+    progress.set_total(n as u64);
+
+    let mut max_size = 0;
+    let mut made = 0;
+    let (mut first, mut second) = (None, None);
+
+    let res = loop {
+        if made == target {
+            first = Some(largest_circuits(&grid));
+        }
+        if let (Some(first), Some(second)) = (first, second) {
+            break (first, second);
+        }
+
+        progress.set_position(max_size as u64);
+        let (pos, closest) = connect_closest(&mut grid)?;
+        made += 1;
+
+        if second.is_none() {
             nodes[&pos].union(&nodes[&closest]);
             max_size = max_size.max(nodes[&pos].root().size.get());
             if max_size == n {
-                break pos.0 * closest.0;
+                second = Some(pos.0 * closest.0);
             }
-        } else {
-            bail!("no more connections")
         }
     };
 
-    bar.finish_and_clear();
-
     Ok(res)
 }
@@ -2,21 +2,22 @@ use std::io::BufRead;
 
 use anyhow::Result;
 
-use crate::Input;
+use crate::solution::solution;
 
-fn parse(input: Input) -> Vec<(i64, i64)> {
-    input
+solution!(9, "Movie Theater");
+
+pub fn parse(input: impl BufRead) -> Result<Vec<(i64, i64)>> {
+    Ok(input
         .lines()
         .map(|s| {
             let s = s.unwrap();
             let mut iter = s.split(",").map(|e| e.parse::<i64>().unwrap());
             (iter.next().unwrap(), iter.next().unwrap())
         })
-        .collect()
+        .collect())
 }
 
-pub fn part1(input: Input) -> Result<i64> {
-    let tiles = parse(input);
+pub fn part1(tiles: &[(i64, i64)]) -> Result<i64> {
 
     Ok(tiles
         .iter()
@@ -63,8 +64,7 @@ fn limits((x, y): (i64, i64), tiles: &[(i64, i64)]) -> ((i64, i64), (i64, i64))
     ((min_x, min_y), (max_x, max_y))
 }
 
-pub fn part2(input: Input) -> Result<i64> {
-    let tiles = parse(input);
+pub fn part2(tiles: &[(i64, i64)]) -> Result<i64> {
 
     let tiles_wrapped: Vec<_> = tiles
         .iter()
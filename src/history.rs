@@ -0,0 +1,135 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::output::Record;
+
+/// One part's timing from a past run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// The git revision the run was built from.
+    pub rev: String,
+    /// When the run happened, in seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u32,
+    pub part: u8,
+    pub input: String,
+    pub duration_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_ns: Option<u64>,
+}
+
+impl Entry {
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.duration_ns)
+    }
+}
+
+/// Timings of past runs, stored one JSON object per line.
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+    /// Whether the file's last line was cut short, so the next entry must
+    /// start a new one.
+    partial: bool,
+}
+
+impl History {
+    /// Loads the history at `path`; a missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("cannot read history {}", path.display()));
+            }
+        };
+        // A damaged line (say, one cut short by Ctrl-C) loses one timing, not
+        // the whole history.
+        let entries = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .filter_map(|(i, l)| match serde_json::from_str(l) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    eprintln!("warning: skipping {}:{}: {}", path.display(), i + 1, e);
+                    None
+                }
+            })
+            .collect();
+        Ok(Self {
+            path: path.to_owned(),
+            entries,
+            partial: !text.is_empty() && !text.ends_with('\n'),
+        })
+    }
+
+    /// The most recent timing for a part on an input.
+    pub fn baseline(&self, day: u32, part: u8, input: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.day == day && e.part == part && e.input == input)
+    }
+
+    /// Appends the timings of the parts in `records` that produced an
+    /// answer by solving rather than from the cache.
+    pub fn append(&mut self, rev: &str, records: &[Record]) -> Result<()> {
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let new: Vec<_> = records
+            .iter()
+            .filter(|r| r.answer.is_solved() && !r.status.is_failure() && !r.cached)
+            .map(|r| Entry {
+                rev: rev.to_owned(),
+                time,
+                day: r.day,
+                part: r.part.number(),
+                input: r.input.clone(),
+                duration_ns: r.duration.unwrap_or_default().as_nanos() as u64,
+                parse_ns: r.parse.map(|d| d.as_nanos() as u64),
+            })
+            .collect();
+        if new.is_empty() {
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("cannot open history {}", self.path.display()))?;
+        if self.partial {
+            writeln!(file)?;
+            self.partial = false;
+        }
+        for entry in &new {
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        }
+        self.entries.extend(new);
+        Ok(())
+    }
+}
+
+/// The current git revision, marked `-dirty` if the tree has uncommitted
+/// changes, or `unknown` outside a git checkout.
+pub fn git_rev() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
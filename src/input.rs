@@ -0,0 +1,101 @@
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+/// Puzzle input as handed to a solution: the whole file, already in memory.
+///
+/// `&[u8]` implements [`BufRead`](std::io::BufRead), so solutions can read it
+/// line by line just as they would a file.
+pub type Input<'a> = &'a [u8];
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Resolves an input name from the command line for `day`.
+    ///
+    /// `-` means stdin. A bare file name such as `test.txt` is looked up in
+    /// `./inputs/<day>/`; anything with a directory component is used as given.
+    pub fn resolve(day: u32, name: &str) -> Self {
+        if name == "-" {
+            return Source::Stdin;
+        }
+        let path = Path::new(name);
+        if path.components().count() > 1 || path.is_absolute() {
+            Source::File(path.to_owned())
+        } else {
+            Source::File(dir(day).join(name))
+        }
+    }
+
+    /// The name the answers file knows this input by: its file name, if it
+    /// is in the day's input directory however it was spelled.
+    pub fn name(&self, day: u32) -> Option<&str> {
+        let Source::File(path) = self else {
+            return None;
+        };
+        let parent = fs::canonicalize(path.parent()?).ok()?;
+        if parent != fs::canonicalize(dir(day)).ok()? {
+            return None;
+        }
+        path.file_name()?.to_str()
+    }
+
+    pub fn read(&self) -> Result<Vec<u8>> {
+        match self {
+            Source::Stdin => {
+                let mut buf = vec![];
+                io::stdin()
+                    .read_to_end(&mut buf)
+                    .context("cannot read input from stdin")?;
+                Ok(buf)
+            }
+            Source::File(path) => {
+                fs::read(path).with_context(|| format!("cannot read input file {}", path.display()))
+            }
+        }
+    }
+}
+
+/// The directory holding a day's inputs.
+pub fn dir(day: u32) -> PathBuf {
+    let mut path = PathBuf::from("./inputs");
+    path.push(day.to_string());
+    path
+}
+
+/// The names of every `*.txt` file in a day's input directory, sorted.
+pub fn list(day: u32) -> Result<Vec<String>> {
+    let dir = dir(day);
+    let entries =
+        fs::read_dir(&dir).with_context(|| format!("cannot list inputs in {}", dir.display()))?;
+    let mut names = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file()
+            && path.extension() == Some("txt".as_ref())
+            && let Some(name) = path.file_name().and_then(|n| n.to_str())
+        {
+            names.push(name.to_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => f.write_str("-"),
+            Source::File(path) => path.display().fmt(f),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+};
+
+thread_local! {
+    /// How many [`catch`] calls are active on this thread.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook that records the message and location of panics
+/// raised inside [`catch`] instead of printing them. Panics anywhere else go to
+/// the previous hook as usual.
+pub fn install_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() == 0 {
+            return default(info);
+        }
+        let msg = payload_message(info.payload());
+        let msg = match info.location() {
+            Some(loc) => format!("panicked at {}: {}", loc, msg),
+            None => format!("panicked: {}", msg),
+        };
+        LAST_PANIC.set(Some(msg));
+    }));
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+/// Runs `f`, turning a panic into an error message naming where it happened.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    CATCHING.set(CATCHING.get() + 1);
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+    res.map_err(|payload| {
+        LAST_PANIC
+            .take()
+            .unwrap_or_else(|| format!("panicked: {}", payload_message(&*payload)))
+    })
+}
@@ -0,0 +1,20 @@
+//! Advent of Code 2025 solutions.
+//!
+//! Each `dayN` module exposes a `parse` function reading its puzzle input from
+//! any [`BufRead`](std::io::BufRead), along with `part1` and `part2` which
+//! solve the parsed puzzle. The [`solution`] registry collects the days for the
+//! `aoc-2025` runner; the build script finds them, so adding a day only takes
+//! adding its file.
+
+pub mod answer;
+pub mod context;
+pub mod input;
+pub mod progress;
+pub mod solution;
+
+pub use context::Context;
+pub use input::Input;
+
+// Declares every `src/dayN.rs` as a module and registers its solution, and
+// defines `SHARED_SOURCE_HASH`.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
@@ -1,75 +1,538 @@
-use std::{fs::File, io::BufReader, path::PathBuf, time::Instant};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
-use anyhow::Result;
-use clap::Parser;
-use colorize::AnsiColor;
-use seq_macro::seq;
+use anyhow::{Context, Result, bail};
+use cache::Cache;
+use clap::{Parser, Subcommand};
 
-seq!(I in 1..=11 {
-    mod day~I;
-});
+use aoc_2025::{
+    answer::Answer,
+    input::{self, Source},
+    solution::{Part, Solution},
+};
+use color::{ColorChoice, Paint};
+use history::History;
+use output::{Format, Printer, Record};
+use runner::{Options, Plan, Runner};
+use select::Selector;
+use verify::Answers;
+use watch::Watcher;
 
-type Input = BufReader<File>;
+mod alloc;
+mod bench;
+mod cache;
+mod color;
+mod history;
+mod isolate;
+mod output;
+mod report;
+mod runner;
+mod scaffold;
+mod select;
+mod verify;
+mod watch;
+
+#[global_allocator]
+static ALLOC: alloc::Counting = alloc::Counting;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    day: Option<u32>,
-    #[arg(short, long)]
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Days to run: `8`, `6..=9` or `6..10`, with `:1` or `:2` to pick a
+    /// part (e.g. `10:1`). All days if none are given.
+    #[arg(value_name = "DAYS")]
+    days: Vec<Selector>,
+    /// Skip these days or parts, given like DAYS and separated by commas
+    #[arg(long, value_name = "DAYS", value_delimiter = ',', global = true)]
+    except: Vec<Selector>,
+    /// List the registered solutions and exit
+    #[arg(short, long, global = true)]
+    list: bool,
+    #[arg(short, long, global = true)]
     test: bool,
-    #[arg(short, long)]
+    /// Input file name within ./inputs/<day>/, a path to a file elsewhere, or
+    /// `-` to read from stdin
+    #[arg(short, long, global = true)]
     input: Option<String>,
-    #[clap(short = '1', long = "part1", overrides_with = "part2")]
+    /// Run on every *.txt file in ./inputs/<day>/, tabulating the results
+    #[arg(short, long, conflicts_with_all = ["test", "input"], global = true)]
+    all_inputs: bool,
+    #[clap(short = '1', long = "part1", overrides_with = "part2", global = true)]
     part1: bool,
-    #[clap(short = '2', long = "part2")]
+    #[clap(short = '2', long = "part2", global = true)]
     part2: bool,
+    /// Check answers against the answers file, exiting with an error on any
+    /// mismatch. Without --test or --input, every input with a recorded answer
+    /// is checked.
+    #[arg(long, global = true)]
+    verify: bool,
+    #[arg(long, global = true, default_value = "answers.txt")]
+    answers: PathBuf,
+    /// Time parsing and each part over N runs (after a warmup) and report statistics
+    #[arg(long, value_name = "N", global = true)]
+    bench: Option<usize>,
+    /// Set a puzzle parameter, overriding the input's .meta sidecar file
+    #[arg(short, long = "param", value_name = "KEY=VALUE", value_parser = parse_param, global = true)]
+    params: Vec<(String, String)>,
+    #[arg(long, value_enum, default_value_t, global = true)]
+    format: Format,
+    /// When to colour output and draw progress bars
+    #[arg(long, value_enum, default_value_t, global = true)]
+    color: ColorChoice,
+    /// Solve days concurrently on N threads (all cores if N is omitted),
+    /// still printing results in day order
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "0", global = true)]
+    jobs: Option<usize>,
+    /// Give up on any part still running after this long (e.g. `500ms`, `10s`,
+    /// `2m`; plain numbers are seconds)
+    #[arg(long, value_parser = parse_duration, global = true)]
+    timeout: Option<Duration>,
+    /// Report allocation count, bytes allocated and peak live bytes per part
+    #[arg(long, global = true)]
+    mem: bool,
+    /// File recording the timings of every run (other than in --watch mode)
+    #[arg(long, default_value = "bench_output.txt", global = true)]
+    history: PathBuf,
+    /// Don't record this run's timings
+    #[arg(long, global = true)]
+    no_history: bool,
+    /// Compare timings with the last ones recorded, flagging parts more than
+    /// PCT percent slower
+    #[arg(long, value_name = "PCT", num_args = 0..=1, default_missing_value = "10", global = true)]
+    compare: Option<f64>,
+    /// File caching answers by input and source code, so unchanged parts
+    /// aren't solved again
+    #[arg(long, global = true, default_value = "answer_cache.txt")]
+    cache: PathBuf,
+    /// Solve every part, ignoring and not updating the answer cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+    /// Run every variant of the selected parts (only those that have
+    /// variants), checking they agree and comparing their times
+    #[arg(long, conflicts_with_all = ["watch", "compare"], global = true)]
+    variants: bool,
+    /// Re-run whenever a file in the day's input directory (or, with
+    /// --verify, the answers file) changes, showing answers that moved
+    #[arg(short, long, global = true)]
+    watch: bool,
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create the module, inputs and answers-file section for a new day
+    New {
+        day: u32,
+        #[arg(long, default_value = "TODO")]
+        title: String,
+    },
+    /// Run days (all of them, or those selected like DAYS above) and write the
+    /// results as a Markdown table and an HTML page, checking answers against
+    /// the answers file if there is one
+    Report {
+        #[arg(value_name = "DAYS")]
+        days: Vec<Selector>,
+        #[arg(long, default_value = "report.md")]
+        markdown: PathBuf,
+        #[arg(long, default_value = "report.html")]
+        html: PathBuf,
+        /// Leave answers out of the report
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Manage the answer cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheAction {
+    /// Delete every cached answer
+    Clear,
+}
+
+fn parse_duration(s: &str) -> Result<Duration> {
+    let (n, unit) = match s.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let n: f64 = n.trim().parse()?;
+    let secs = match unit {
+        "ms" => n / 1000.0,
+        "s" => n,
+        "m" => n * 60.0,
+        _ => bail!("unknown unit '{}'", unit),
+    };
+    Ok(Duration::try_from_secs_f64(secs)?)
+}
+
+fn parse_param(s: &str) -> Result<(String, String)> {
+    match s.split_once('=') {
+        Some((key, value)) => Ok((key.trim().to_owned(), value.trim().to_owned())),
+        None => bail!("expected KEY=VALUE"),
+    }
+}
+
+impl Args {
+    fn parts(&self) -> Vec<Part> {
+        Part::ALL
+            .into_iter()
+            .filter(|&p| match p {
+                Part::One => !self.part2,
+                Part::Two => !self.part1,
+            })
+            .collect()
+    }
 
-    macro_rules! run {
-        ($fn:expr, $path:expr, $msg:expr, $colour:ident) => {
-            let start = Instant::now();
-            let output = $fn(BufReader::new(
-                File::open($path).expect("Input file cannot be opened!"),
-            ))?;
-            let duration = Instant::now().duration_since(start);
-            println!(
-                "{time} {msg} {output}",
-                time = format!("[{:>10?}]", duration).b_black(),
-                msg = $msg.bold().$colour(),
-                output = output
-            );
+    /// The input file named on the command line, if any.
+    fn input_name(&self) -> Option<&str> {
+        match &self.input {
+            Some(s) => Some(s),
+            None if self.test => Some("test.txt"),
+            None => None,
+        }
+    }
+
+    fn plan(&self, day: u32, parts: &[Part], answers: Option<&Answers>) -> Result<Plan> {
+        let plan: Plan = if self.all_inputs {
+            input::list(day)?
+                .into_iter()
+                .map(|input| (input, parts.to_vec()))
+                .collect()
+        } else {
+            match (self.input_name(), answers) {
+                (None, Some(answers)) if !answers.inputs(day).is_empty() => answers
+                    .inputs(day)
+                    .into_iter()
+                    .map(|input| {
+                        let parts = parts
+                            .iter()
+                            .copied()
+                            .filter(|&p| answers.get(day, p, input).is_some())
+                            .collect();
+                        (input.to_owned(), parts)
+                    })
+                    .collect(),
+                (name, _) => vec![(name.unwrap_or("input.txt").to_owned(), parts.to_vec())],
+            }
         };
+
+        // Examples can say which parts they're for in their sidecar.
+        let mut filtered = vec![];
+        for (input, mut parts) in plan {
+            if let Source::File(path) = Source::resolve(day, &input) {
+                let supported = aoc_2025::Context::load(&path)?.parts()?;
+                parts.retain(|p| supported.contains(p));
+            }
+            if !parts.is_empty() {
+                filtered.push((input, parts));
+            }
+        }
+        Ok(filtered)
     }
+}
+
+/// Answers from the previous run in watch mode, keyed by day, part and input.
+type Previous = HashMap<(u32, Part, String), Answer>;
+
+/// Solves the selected days once, printing the results as they come in, and
+/// returns every record.
+fn run(
+    args: &Args,
+    selection: &[(&'static dyn Solution, Vec<Part>)],
+    previous: &Previous,
+    history: Option<&History>,
+    cache: Option<&Cache>,
+) -> Result<Vec<Record>> {
+    let report = matches!(args.command, Some(Command::Report { .. }));
+    let answers = match args.verify || (report && args.answers.exists()) {
+        true => Some(Answers::load(&args.answers)?),
+        false => None,
+    };
+    // Reports check answers without running every input that has one.
+    let plan_answers = answers.as_ref().filter(|_| args.verify);
+    let days: Vec<_> = selection
+        .iter()
+        .map(|(s, parts)| Ok((*s, args.plan(s.day(), parts, plan_answers)?)))
+        .collect::<Result<_>>()?;
+    let runner = Runner {
+        answers: answers.as_ref(),
+        params: &args.params,
+        cache,
+        options: Options {
+            bench: args.bench,
+            timeout: args.timeout,
+            mem: args.mem,
+            // Bars would garble machine-readable output, skew benchmarks and
+            // interleave between days solved in parallel.
+            progress: args.color.enabled(&io::stderr())
+                && args.format == Format::Text
+                && args.bench.is_none()
+                && args.jobs.is_none(),
+            variants: args.variants,
+        },
+    };
+    let mut printer = Printer::new(args.format);
+    let table = args.all_inputs && args.format == Format::Text;
+    let mut records = vec![];
+    let mut cpu_time = Duration::ZERO;
+    let start = Instant::now();
 
-    seq!(I in 1..=11 {
-        match args.day {
-            Some(I) | None => {
-                let mut path = PathBuf::from("./inputs");
-                path.push(I.to_string());
-                path.push(match &args.input {
-                    Some(s) => s,
-                    None => {
-                        if args.test {
-                            "test.txt"
-                        } else {
-                            "input.txt"
-                        }
-                    }
-                });
-                println!("{}", format!("### Day {} ###", I).bold().green());
-                if !args.part2 {
-                    run!(day~I::part1, &path, "Part 1:", blue);
+    runner.run_all(&days, args.jobs, |runs| {
+        if table && !args.variants {
+            printer.table(&runs);
+        }
+        for run in runs {
+            if args.variants {
+                cpu_time += run.parse.map(|t| t.duration).unwrap_or_default();
+                cpu_time += run
+                    .records
+                    .iter()
+                    .filter_map(|r| r.duration)
+                    .sum::<Duration>();
+                match args.format {
+                    Format::Text => printer.variants(&run),
+                    _ => run.records.iter().for_each(|r| printer.record(r)),
+                }
+                records.extend(run.records);
+                continue;
+            }
+            if !table {
+                printer.day(run.day, args.verify.then_some(run.input.as_str()));
+            }
+            if let Some(parse) = &run.parse {
+                cpu_time += parse.duration;
+                if !table {
+                    printer.parse(parse);
+                }
+            }
+            for mut record in run.records {
+                if !(record.combined && record.part == Part::Two) {
+                    cpu_time += record.duration.unwrap_or_default();
+                }
+                if args.compare.is_some()
+                    && !record.cached
+                    && let Some(history) = history
+                    && let Some(entry) =
+                        history.baseline(record.day, record.part.number(), &record.input)
+                {
+                    record.baseline = Some(entry.duration());
                 }
-                if !args.part1 {
-                    run!(day~I::part2, &path, "Part 2:", magenta);
+                if table {
+                    records.push(record);
+                    continue;
+                }
+                printer.record(&record);
+                if let Some(threshold) = args.compare {
+                    printer.compare(&record, threshold);
+                }
+                if let Some(before) = previous.get(&(record.day, record.part, record.input.clone()))
+                    && *before != record.answer
+                {
+                    printer.changed(before);
+                }
+                records.push(record);
+            }
+        }
+    })?;
+
+    if args.jobs.is_some() {
+        printer.timing(Instant::now().duration_since(start), cpu_time);
+    }
+
+    let failures: Vec<_> = records
+        .iter()
+        .filter(|r| r.status.is_failure())
+        .cloned()
+        .collect();
+    printer.summary(&failures);
+    if let Some(threshold) = args.compare {
+        printer.regressions(&records, threshold);
+    }
+    Ok(records)
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    isolate::install_hook();
+    color::init(args.color);
+
+    if let Some(Command::New { day, title }) = &args.command {
+        scaffold::new(*day, title, &args.answers)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Cache {
+        action: CacheAction::Clear,
+    }) = &args.command
+    {
+        match cache::clear(&args.cache)? {
+            true => println!("deleted {}", args.cache.display()),
+            false => println!("no cache at {}", args.cache.display()),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let include = match &args.command {
+        Some(Command::Report { days, .. }) => days,
+        _ => &args.days,
+    };
+    let mut selection = select::select(include, &args.except, &args.parts())?;
+    if args.variants {
+        for (solution, parts) in &mut selection {
+            parts.retain(|&p| !solution.variants(p).is_empty());
+        }
+        selection.retain(|(_, parts)| !parts.is_empty());
+        if selection.is_empty() {
+            bail!("none of the selected parts have variants");
+        }
+    }
+
+    if args.list {
+        for (solution, parts) in selection {
+            let mut line = format!("{:>2}: {}", solution.day(), solution.title());
+            for part in parts {
+                let variants = solution.variants(part);
+                if !variants.is_empty() {
+                    line += &format!(" ({} variants: {})", part, variants.join(", "));
                 }
-            },
-            _ => ()
+            }
+            println!("{}", line);
         }
-    });
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    Ok(())
+    if args.input_name() == Some("-") && (selection.len() > 1 || args.watch) {
+        bail!("reading input from stdin requires a single day and no --watch");
+    }
+    if args.variants && matches!(args.command, Some(Command::Report { .. })) {
+        bail!("--variants cannot be used to write a report");
+    }
+    if args.watch && matches!(args.command, Some(Command::Report { .. })) {
+        bail!("--watch cannot be used to write a report");
+    }
+    if args.watch && args.format != Format::Text {
+        bail!("--watch only supports text output");
+    }
+    if args.mem {
+        alloc::enable();
+    }
+
+    let mut history = match args.no_history && args.compare.is_none() {
+        true => None,
+        false => Some(History::load(&args.history)?),
+    };
+
+    // Benchmarks, comparisons and reports are about solving, so they always
+    // solve.
+    let report = matches!(args.command, Some(Command::Report { .. }));
+    let bypass = args.bench.is_some() || args.mem || args.variants || args.compare.is_some();
+    let cache = match args.no_cache || bypass || report {
+        true => None,
+        false => Some(Cache::load(&args.cache)?),
+    };
+
+    if !args.watch {
+        let records = run(
+            &args,
+            &selection,
+            &Previous::default(),
+            history.as_ref(),
+            cache.as_ref(),
+        )?;
+        if let Some(cache) = &cache {
+            cache.save()?;
+        }
+        // Variants aren't what a day is timed by, so stay out of the history.
+        if let Some(history) = &mut history
+            && !args.no_history
+            && !args.variants
+        {
+            history.append(&history::git_rev(), &records)?;
+        }
+        if let Some(Command::Report {
+            markdown,
+            html,
+            redact,
+            ..
+        }) = &args.command
+        {
+            for (path, text) in [
+                (markdown, report::markdown(&records, *redact)),
+                (html, report::html(&records, *redact)),
+            ] {
+                fs::write(path, text)
+                    .with_context(|| format!("cannot write report {}", path.display()))?;
+                println!("wrote {}", path.display());
+            }
+        }
+        let regressed = |r: &Record| args.compare.is_some_and(|t| r.regressed(t));
+        if records
+            .iter()
+            .any(|r| r.status.is_failure() || regressed(r))
+        {
+            return Ok(ExitCode::FAILURE);
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let name = args.input_name().unwrap_or("input.txt");
+    let mut paths: Vec<_> = selection
+        .iter()
+        .filter_map(|(s, _)| match Source::resolve(s.day(), name) {
+            Source::File(path) => path.parent().map(Path::to_owned),
+            Source::Stdin => None,
+        })
+        .collect();
+    if args.verify {
+        paths.push(args.answers.clone());
+    }
+    let mut watcher = Watcher::new(paths);
+    let mut previous = Previous::default();
+    loop {
+        watch::clear_screen();
+        let res = run(
+            &args,
+            &selection,
+            &previous,
+            history.as_ref(),
+            cache.as_ref(),
+        )
+        .and_then(|records| {
+            if let Some(cache) = &cache {
+                cache.save()?;
+            }
+            Ok(records)
+        });
+        match res {
+            Ok(records) => {
+                previous = records
+                    .into_iter()
+                    .map(|r| ((r.day, r.part, r.input), r.answer))
+                    .collect();
+            }
+            Err(e) => eprintln!("{}", format!("error: {:#}", e).red()),
+        }
+        let watched: Vec<_> = watcher
+            .paths()
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        println!(
+            "{}",
+            format!(
+                "watching {} for changes; Ctrl-C to stop",
+                watched.join(", ")
+            )
+            .b_black()
+        );
+        watcher.wait();
+    }
 }
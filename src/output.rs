@@ -0,0 +1,576 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use aoc_2025::{answer::Answer, solution::Part};
+
+use crate::{
+    alloc::MemStats,
+    bench::Stats,
+    color::Paint,
+    runner::{InputRun, Timing},
+    verify::Verdict,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Coloured, human-readable output
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// The outcome of a part, as reported in machine-readable output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Solved, with no expected answer to check against.
+    Solved,
+    Unsolved,
+    Pass,
+    Fail,
+    Unknown,
+    /// Did not finish within the time limit.
+    Timeout,
+    /// Returned an error or panicked.
+    Error,
+}
+
+impl Status {
+    pub fn new(answer: &Answer, verdict: Option<Verdict>) -> Self {
+        match verdict {
+            Some(Verdict::Pass) => Status::Pass,
+            Some(Verdict::Fail) => Status::Fail,
+            _ if !answer.is_solved() => Status::Unsolved,
+            Some(Verdict::Unknown) => Status::Unknown,
+            None => Status::Solved,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Unknown => "unknown",
+            Status::Timeout => "timeout",
+            Status::Error => "error",
+        }
+    }
+
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Fail | Status::Timeout | Status::Error)
+    }
+}
+
+/// The result of running one part on one input.
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub day: u32,
+    #[serde(serialize_with = "serialize_part")]
+    pub part: Part,
+    /// Which implementation of the part produced this record, when comparing
+    /// variants.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<&'static str>,
+    pub input: String,
+    pub answer: Answer,
+    /// How long solving took, or `None` for an answer from the cache.
+    #[serde(rename = "duration_ns", serialize_with = "serialize_opt_nanos")]
+    pub duration: Option<Duration>,
+    /// How long parsing the input took; shared by all parts of the input.
+    #[serde(
+        rename = "parse_ns",
+        serialize_with = "serialize_opt_nanos",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parse: Option<Duration>,
+    /// Whether both parts were solved in one go, in which case `duration` is
+    /// the time taken for the pair.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub combined: bool,
+    /// Whether the answer came from the cache rather than being solved.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// The last recorded duration for this part and input, when comparing
+    /// against the history.
+    #[serde(
+        rename = "baseline_ns",
+        serialize_with = "serialize_opt_nanos",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub baseline: Option<Duration>,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bench: Option<Stats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem: Option<MemStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// How much slower this run was than the baseline, as a fraction (0.25
+    /// for 25% slower; negative if faster).
+    pub fn slowdown(&self) -> Option<f64> {
+        let baseline = self.baseline.filter(|b| !b.is_zero())?;
+        Some(self.duration?.as_secs_f64() / baseline.as_secs_f64() - 1.0)
+    }
+
+    /// Whether this run was slower than the baseline by more than
+    /// `threshold` percent.
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.slowdown().is_some_and(|s| s * 100.0 > threshold)
+    }
+}
+
+fn serialize_part<S: serde::Serializer>(part: &Part, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u8(part.number())
+}
+
+pub(crate) fn serialize_nanos<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u128(d.as_nanos())
+}
+
+fn serialize_opt_nanos<S: serde::Serializer>(
+    d: &Option<Duration>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    match d {
+        Some(d) => serialize_nanos(d, s),
+        None => s.serialize_none(),
+    }
+}
+
+/// Writes results to stdout in the selected [`Format`].
+pub struct Printer {
+    format: Format,
+    header_done: bool,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            header_done: false,
+        }
+    }
+
+    /// Announces the start of a day; only shown in text output.
+    pub fn day(&self, day: u32, input: Option<&str>) {
+        if self.format != Format::Text {
+            return;
+        }
+        let banner = match input {
+            Some(input) => format!("### Day {} ({}) ###", day, input),
+            None => format!("### Day {} ###", day),
+        };
+        println!("{}", banner.bold().green());
+    }
+
+    /// Shows how long parsing an input took; only shown in text output, as
+    /// the machine-readable formats carry it on every record.
+    pub fn parse(&self, timing: &Timing) {
+        if self.format != Format::Text {
+            return;
+        }
+        println!(
+            "{}{} {}",
+            format!("[{:>10?}]", timing.duration).b_black(),
+            mem_column(timing.mem.as_ref()),
+            "Parse".b_black()
+        );
+        if let Some(stats) = &timing.bench {
+            println!("{:13}{}", "", stats.to_string().b_black());
+        }
+    }
+
+    pub fn record(&mut self, record: &Record) {
+        match self.format {
+            Format::Text => print_text(record),
+            Format::Json => println!(
+                "{}",
+                serde_json::to_string(record).expect("records are always serializable")
+            ),
+            Format::Csv => {
+                if !self.header_done {
+                    println!(
+                        "day,part,input,answer,duration_ns,status,error,allocations,allocated_bytes,peak_bytes,parse_ns,combined,baseline_ns,variant,cached"
+                    );
+                    self.header_done = true;
+                }
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                    record.day,
+                    record.part.number(),
+                    csv_field(&record.input),
+                    match record.answer.is_solved() {
+                        true => csv_field(&record.answer.to_string()),
+                        false => String::new(),
+                    },
+                    opt(record.duration.map(|d| d.as_nanos())),
+                    record.status.as_str(),
+                    csv_field(record.error.as_deref().unwrap_or_default()),
+                    opt(record.mem.map(|m| m.allocations)),
+                    opt(record.mem.map(|m| m.allocated_bytes)),
+                    opt(record.mem.map(|m| m.peak_bytes)),
+                    opt(record.parse.map(|d| d.as_nanos())),
+                    record.combined,
+                    opt(record.baseline.map(|d| d.as_nanos())),
+                    record.variant.unwrap_or_default(),
+                    record.cached
+                );
+            }
+        }
+    }
+
+    /// Shows a day's results as a table with a row per input file; only shown
+    /// in text output.
+    pub fn table(&self, runs: &[InputRun]) {
+        if self.format != Format::Text {
+            return;
+        }
+        let Some(first) = runs.first() else {
+            return;
+        };
+        self.day(first.day, None);
+
+        let mut parts: Vec<_> = runs
+            .iter()
+            .flat_map(|r| &r.records)
+            .map(|r| r.part)
+            .collect();
+        parts.sort();
+        parts.dedup();
+
+        let mut header: Vec<Cell> = vec![("input".to_owned(), bold), ("parse".to_owned(), bold)];
+        for part in &parts {
+            header.push((part.to_string(), bold));
+            header.push(("time".to_owned(), bold));
+        }
+        let mut rows = vec![header];
+        for run in runs {
+            let parse = run.parse.map(|t| format!("{:.2?}", t.duration));
+            let mut row: Vec<Cell> =
+                vec![(run.input.clone(), plain), (parse.unwrap_or_default(), dim)];
+            for &part in &parts {
+                match run.records.iter().find(|r| r.part == part) {
+                    Some(record) => {
+                        row.push(table_cell(record));
+                        let time = if record.cached {
+                            "cached".to_owned()
+                        } else if record.combined && part == Part::Two {
+                            "combined".to_owned()
+                        } else {
+                            format!("{:.2?}", record.duration.unwrap_or_default())
+                        };
+                        row.push((time, dim));
+                    }
+                    None => row.extend([(String::new(), plain as Style), (String::new(), plain)]),
+                }
+            }
+            rows.push(row);
+        }
+        print_rows(rows);
+    }
+
+    /// Shows how the variants of each part fared on an input, with their
+    /// times relative to the fastest; only shown in text output. Variants
+    /// that disagreed or failed are listed in the [`summary`](Printer::summary).
+    pub fn variants(&self, run: &InputRun) {
+        if self.format != Format::Text {
+            return;
+        }
+        self.day(run.day, Some(&run.input));
+        if let Some(parse) = &run.parse {
+            self.parse(parse);
+        }
+
+        let header = ["part", "variant", "answer", "time", "relative"];
+        let mut rows = vec![header.map(|h| (h.to_owned(), bold as Style)).to_vec()];
+        for part in Part::ALL {
+            let records: Vec<_> = run.records.iter().filter(|r| r.part == part).collect();
+            let fastest = records
+                .iter()
+                .filter(|r| r.answer.is_solved() && !r.status.is_failure())
+                .filter_map(|r| r.duration)
+                .min();
+            for record in records {
+                let relative = match fastest {
+                    Some(fastest) if record.answer.is_solved() && !record.status.is_failure() => {
+                        match fastest.is_zero() {
+                            true => String::new(),
+                            false => format!(
+                                "{:.2}x",
+                                record.duration.unwrap_or_default().as_secs_f64()
+                                    / fastest.as_secs_f64()
+                            ),
+                        }
+                    }
+                    _ => String::new(),
+                };
+                rows.push(vec![
+                    (part.to_string(), bold),
+                    (record.variant.unwrap_or_default().to_owned(), plain),
+                    table_cell(record),
+                    (format!("{:.2?}", record.duration.unwrap_or_default()), dim),
+                    (relative, dim),
+                ]);
+            }
+        }
+        print_rows(rows);
+    }
+
+    /// Shows what a part's answer was before it changed; only shown in text
+    /// output.
+    pub fn changed(&self, before: &Answer) {
+        if self.format != Format::Text {
+            return;
+        }
+        println!(
+            "{:13}{} {}",
+            "",
+            "was:".b_black(),
+            before.to_string().yellow()
+        );
+    }
+
+    /// Shows how a part's time compares to its baseline, highlighting changes
+    /// beyond `threshold` percent; only shown in text output.
+    pub fn compare(&self, record: &Record, threshold: f64) {
+        let (Some(baseline), Some(slowdown)) = (record.baseline, record.slowdown()) else {
+            return;
+        };
+        if self.format != Format::Text
+            || record.cached
+            || (record.combined && record.part == Part::Two)
+        {
+            return;
+        }
+        let msg = format!("{:+.1}% vs {:.2?}", slowdown * 100.0, baseline);
+        let msg = if slowdown * 100.0 > threshold {
+            msg.red()
+        } else if -slowdown * 100.0 > threshold {
+            msg.green()
+        } else {
+            msg.b_black()
+        };
+        println!("{:13}{}", "", msg);
+    }
+
+    /// Compares the wall-clock time of a parallel run with the time spent
+    /// solving across all threads.
+    pub fn timing(&self, wall: Duration, cpu: Duration) {
+        let msg = format!(
+            "wall {:.2?}, solving {:.2?} ({:.1}x)",
+            wall,
+            cpu,
+            cpu.as_secs_f64() / wall.as_secs_f64()
+        );
+        match self.format {
+            Format::Text => println!("{}", msg.b_black()),
+            _ => eprintln!("{}", msg),
+        }
+    }
+
+    /// Lists the parts that failed, if any. Machine-readable formats send
+    /// this to stderr so stdout stays parseable.
+    pub fn summary(&self, failures: &[Record]) {
+        if failures.is_empty() {
+            return;
+        }
+        let mut lines = vec![format!("{} part(s) failed:", failures.len())];
+        for r in failures {
+            let reason = match (&r.status, &r.error, &r.expected) {
+                (Status::Fail, _, Some(expected)) => {
+                    format!("expected {}, got {}", expected, r.answer)
+                }
+                (Status::Timeout, _, _) => {
+                    format!("timed out after {:.2?}", r.duration.unwrap_or_default())
+                }
+                (_, Some(error), _) => error.clone(),
+                _ => r.status.as_str().to_owned(),
+            };
+            let part = match r.variant {
+                Some(variant) => format!("{} {}", r.part, variant),
+                None => r.part.to_string(),
+            };
+            lines.push(format!(
+                "  Day {} {} ({}): {}",
+                r.day, part, r.input, reason
+            ));
+        }
+        match self.format {
+            Format::Text => {
+                println!("{}", lines[0].to_string().bold().red());
+                for line in &lines[1..] {
+                    println!("{}", line.to_string().red());
+                }
+            }
+            _ => lines.iter().for_each(|l| eprintln!("{}", l)),
+        }
+    }
+
+    /// Lists the parts that got slower than their baseline by more than
+    /// `threshold` percent, if any. Like [`summary`](Printer::summary), this
+    /// goes to stderr for machine-readable formats.
+    pub fn regressions(&self, records: &[Record], threshold: f64) {
+        let regressed: Vec<_> = records
+            .iter()
+            .filter(|r| r.regressed(threshold) && !(r.combined && r.part == Part::Two))
+            .collect();
+        if regressed.is_empty() {
+            return;
+        }
+        let mut lines = vec![format!(
+            "{} part(s) got more than {}% slower:",
+            regressed.len(),
+            threshold
+        )];
+        for r in regressed {
+            lines.push(format!(
+                "  Day {} {} ({}): {:.2?} -> {:.2?} ({:+.1}%)",
+                r.day,
+                r.part,
+                r.input,
+                r.baseline.unwrap_or_default(),
+                r.duration.unwrap_or_default(),
+                r.slowdown().unwrap_or_default() * 100.0
+            ));
+        }
+        match self.format {
+            Format::Text => {
+                println!("{}", lines[0].to_string().bold().red());
+                for line in &lines[1..] {
+                    println!("{}", line.to_string().red());
+                }
+            }
+            _ => lines.iter().for_each(|l| eprintln!("{}", l)),
+        }
+    }
+}
+
+fn opt(n: Option<impl ToString>) -> String {
+    n.map(|n| n.to_string()).unwrap_or_default()
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+type Style = fn(String) -> String;
+type Cell = (String, Style);
+
+fn plain(s: String) -> String {
+    s
+}
+
+fn dim(s: String) -> String {
+    s.b_black()
+}
+
+fn bold(s: String) -> String {
+    s.bold()
+}
+
+/// Prints rows of cells as columns, each as wide as its widest cell.
+fn print_rows(rows: Vec<Vec<Cell>>) {
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (width, (text, _)) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text.chars().count());
+        }
+    }
+    for row in rows {
+        let cells: Vec<_> = row
+            .into_iter()
+            .zip(&widths)
+            .map(|((text, style), &width)| style(format!("{:width$}", text)))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn table_cell(record: &Record) -> Cell {
+    let answer = &record.answer;
+    match record.status {
+        Status::Solved => (answer.to_string(), plain),
+        Status::Unsolved => (answer.to_string(), dim),
+        Status::Pass => (format!("{} PASS", answer), |s| s.green()),
+        Status::Fail => (format!("{} FAIL", answer), |s| s.red()),
+        Status::Unknown => (format!("{} ????", answer), plain),
+        Status::Timeout => ("TIMEOUT".to_owned(), |s| s.red()),
+        Status::Error => ("ERROR".to_owned(), |s| s.red()),
+    }
+}
+
+fn mem_column(mem: Option<&MemStats>) -> String {
+    match mem {
+        Some(mem) => format!(" [{:>42}]", mem.to_string()).b_black(),
+        None => String::new(),
+    }
+}
+
+fn print_text(record: &Record) {
+    // Cached answers, and the second half of a combined solve, have no timing
+    // of their own.
+    let shared = record.combined && record.part == Part::Two;
+    let msg = format!("{}:", record.part).bold();
+    println!(
+        "{time}{mem} {msg} {output}{verdict}",
+        time = if record.cached {
+            format!("[{:>10}]", "cached").b_black()
+        } else if shared {
+            format!("[{:>10}]", "combined").b_black()
+        } else {
+            format!("[{:>10?}]", record.duration.unwrap_or_default()).b_black()
+        },
+        mem = match (&record.mem, shared) {
+            (Some(_), true) => format!(" [{:42}]", "").b_black(),
+            (mem, _) => mem_column(mem.as_ref()),
+        },
+        msg = match record.part {
+            Part::One => msg.blue(),
+            Part::Two => msg.magenta(),
+        },
+        output = if record.status == Status::Timeout {
+            "TIMEOUT".bold().red()
+        } else if record.status == Status::Error {
+            "ERROR".bold().red()
+        } else if record.answer.is_solved() {
+            record.answer.to_string()
+        } else {
+            record.answer.to_string().b_black()
+        },
+        verdict = match record.status {
+            Status::Solved | Status::Unsolved | Status::Timeout | Status::Error => String::new(),
+            Status::Pass => format!(" {}", "PASS".bold().green()),
+            Status::Fail => format!(" {}", "FAIL".bold().red()),
+            Status::Unknown => format!(" {}", "????".b_black()),
+        }
+    );
+    if let Some(expected) = &record.expected
+        && record.status == Status::Fail
+    {
+        println!("{:13}{} {}", "", "expected:".b_black(), expected);
+    }
+    if let Some(error) = &record.error {
+        println!("{:13}{}", "", error.to_string().red());
+    }
+    if let Some(stats) = &record.bench
+        && !shared
+    {
+        println!("{:13}{}", "", stats.to_string().b_black());
+    }
+}
@@ -0,0 +1,54 @@
+//! Progress reporting for long-running parts.
+
+use std::{borrow::Cow, fmt};
+
+use indicatif::ProgressBar;
+
+/// A part's handle for reporting how far along it is, found on its
+/// [`Context`](crate::Context). The runner decides whether and how to draw
+/// it; by default nothing is shown.
+#[derive(Clone)]
+pub struct Progress {
+    bar: ProgressBar,
+}
+
+impl Progress {
+    /// Reports progress through `bar`.
+    pub fn new(bar: ProgressBar) -> Self {
+        Self { bar }
+    }
+
+    /// Sets how many steps there are in total.
+    pub fn set_total(&self, total: u64) {
+        self.bar.set_length(total);
+    }
+
+    pub fn inc(&self, steps: u64) {
+        self.bar.inc(steps);
+    }
+
+    /// Sets how many steps have been done, for parts that measure progress
+    /// by something other than counting steps one at a time.
+    pub fn set_position(&self, steps: u64) {
+        self.bar.set_position(steps);
+    }
+
+    pub fn message(&self, msg: impl Into<Cow<'static, str>>) {
+        self.bar.set_message(msg);
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new(ProgressBar::hidden())
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("position", &self.bar.position())
+            .field("total", &self.bar.length())
+            .finish()
+    }
+}
@@ -0,0 +1,158 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc_2025::solution::{self, Part};
+
+use crate::output::{Record, Status};
+
+/// Width of the longest bar in the Markdown chart, in characters.
+const BAR_WIDTH: usize = 20;
+
+/// One row of the report: a part's results with the bits of its record that
+/// need formatting.
+struct Row<'a> {
+    record: &'a Record,
+    title: &'static str,
+    answer: String,
+    /// Solving time, or `None` for cached answers and the second half of a
+    /// combined solve.
+    solve: Option<Duration>,
+    /// Solving time as a fraction of the slowest part's.
+    share: f64,
+}
+
+fn rows(records: &[Record], redact: bool) -> Vec<Row<'_>> {
+    let solve = |r: &Record| r.duration.filter(|_| !(r.combined && r.part == Part::Two));
+    let slowest = records
+        .iter()
+        .filter_map(solve)
+        .max()
+        .unwrap_or_default()
+        .as_secs_f64();
+    records
+        .iter()
+        .map(|record| Row {
+            record,
+            title: solution::get(record.day).map_or("", |s| s.title()),
+            answer: match (record.answer.is_solved(), redact) {
+                (true, true) => "redacted".to_owned(),
+                _ => record.answer.to_string(),
+            },
+            solve: solve(record),
+            share: match slowest > 0.0 {
+                true => solve(record).unwrap_or_default().as_secs_f64() / slowest,
+                false => 0.0,
+            },
+        })
+        .collect()
+}
+
+fn duration(row: &Row) -> String {
+    match row.solve {
+        Some(d) => format!("{:.2?}", d),
+        None if row.record.cached => "cached".to_owned(),
+        None => "with part 1".to_owned(),
+    }
+}
+
+fn status(record: &Record) -> &'static str {
+    match record.status {
+        Status::Unknown => "unverified",
+        Status::Solved => "",
+        status => status.as_str(),
+    }
+}
+
+/// Renders the results as a Markdown table, with a bar of block characters
+/// showing each part's share of the slowest time.
+pub fn markdown(records: &[Record], redact: bool) -> String {
+    let mut out = String::from(
+        "| Day | Title | Part | Input | Answer | Parse | Solve | Status | |\n\
+         |----:|-------|-----:|-------|-------:|------:|------:|--------|-|\n",
+    );
+    for row in rows(records, redact) {
+        let r = row.record;
+        let blocks = (row.share * BAR_WIDTH as f64).round() as usize;
+        let bar = match (blocks, row.solve) {
+            (0, Some(d)) if !d.is_zero() => "▏".to_owned(),
+            _ => "█".repeat(blocks),
+        };
+        writeln!(
+            out,
+            "| {} | {} | {} | `{}` | {} | {} | {} | {} | {} |",
+            r.day,
+            row.title,
+            r.part.number(),
+            r.input,
+            row.answer.replace('|', "\\|"),
+            r.parse.map(|d| format!("{:.2?}", d)).unwrap_or_default(),
+            duration(&row),
+            status(r),
+            bar
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the results as a standalone HTML page, with a bar chart of each
+/// part's share of the slowest time.
+pub fn html(records: &[Record], redact: bool) -> String {
+    let mut out = String::from(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code 2025</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.25em 0.75em; text-align: left; }
+tr:nth-child(even) { background: #f4f4f4; }
+td.num { text-align: right; font-family: monospace; }
+td.chart { width: 20em; }
+.bar { background: #4a7ebb; height: 1em; min-width: 1px; }
+.pass { color: #2a7a2a; }
+.fail, .timeout, .error { color: #b22222; font-weight: bold; }
+</style>
+</head>
+<body>
+<h1>Advent of Code 2025</h1>
+<table>
+<tr><th>Day</th><th>Title</th><th>Part</th><th>Input</th><th>Answer</th><th>Parse</th><th>Solve</th><th>Status</th><th>Relative time</th></tr>
+"#,
+    );
+    for row in rows(records, redact) {
+        let r = row.record;
+        let bar = match row.solve {
+            Some(_) => format!(
+                r#"<div class="bar" style="width: {:.1}%"></div>"#,
+                row.share * 100.0
+            ),
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            r#"<tr><td class="num">{}</td><td>{}</td><td class="num">{}</td><td><code>{}</code></td><td class="num">{}</td><td class="num">{}</td><td class="num">{}</td><td class="{}">{}</td><td class="chart">{}</td></tr>"#,
+            r.day,
+            escape(row.title),
+            r.part.number(),
+            escape(&r.input),
+            escape(&row.answer),
+            r.parse.map(|d| format!("{:.2?}", d)).unwrap_or_default(),
+            duration(&row),
+            r.status.as_str(),
+            status(r),
+            bar
+        )
+        .unwrap();
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
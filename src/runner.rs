@@ -0,0 +1,649 @@
+use std::{
+    collections::BTreeMap,
+    iter,
+    sync::{
+        Arc,
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use aoc_2025::{
+    Context,
+    answer::Answer,
+    input::Source,
+    progress::Progress,
+    solution::{Part, Puzzle, Solution},
+};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+
+use crate::{
+    alloc::{self, MemStats},
+    bench,
+    cache::{self, Cache},
+    isolate,
+    output::{Record, Status},
+    verify::Answers,
+};
+
+/// The inputs to run for a day, each with the parts to run on it.
+pub type Plan = Vec<(String, Vec<Part>)>;
+
+/// The records produced by running one day's plan on one input.
+#[derive(Debug)]
+pub struct InputRun {
+    pub day: u32,
+    pub input: String,
+    /// How long parsing took, unless the input couldn't be read.
+    pub parse: Option<Timing>,
+    pub records: Vec<Record>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    /// Benchmark each step over this many runs.
+    pub bench: Option<usize>,
+    pub timeout: Option<Duration>,
+    /// Count allocations; requires [`alloc::enable`].
+    pub mem: bool,
+    /// Draw progress bars for each day and the part being solved.
+    pub progress: bool,
+    /// Run every variant of each part, rather than just the default one.
+    pub variants: bool,
+}
+
+/// The name given to a part's default implementation when comparing it with
+/// its variants.
+pub const DEFAULT_VARIANT: &str = "default";
+
+pub struct Runner<'a> {
+    pub answers: Option<&'a Answers>,
+    /// Puzzle parameters overriding those in the inputs' sidecar files.
+    pub params: &'a [(String, String)],
+    /// Where to look up answers before solving, and remember them after.
+    pub cache: Option<&'a Cache>,
+    pub options: Options,
+}
+
+/// How long a step (parsing, or solving a part) took. On failure, only the
+/// duration up to the failure is known.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timing {
+    pub duration: Duration,
+    pub bench: Option<bench::Stats>,
+    pub mem: Option<MemStats>,
+}
+
+/// Why a step produced no result.
+#[derive(Clone)]
+enum Failure {
+    /// It returned an error or panicked.
+    Error(String),
+    TimedOut,
+}
+
+struct Step<T> {
+    result: Result<T, Failure>,
+    timing: Timing,
+}
+
+impl<T> Step<T> {
+    fn failed(failure: Failure, duration: Duration) -> Self {
+        Step {
+            result: Err(failure),
+            timing: Timing {
+                duration,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Fails any variant whose answer differs from the first one solved for the
+/// same part, unless the answers file has already passed judgement on it.
+fn check_agreement(records: &mut [Record]) {
+    for part in Part::ALL {
+        let mut reference = None;
+        for record in records.iter_mut().filter(|r| r.part == part) {
+            if !record.answer.is_solved() || record.status.is_failure() {
+                continue;
+            }
+            let reference = reference.get_or_insert_with(|| record.answer.clone());
+            if record.answer != *reference && record.status != Status::Pass {
+                record.status = Status::Fail;
+                record.expected = Some(reference.clone());
+            }
+        }
+    }
+}
+
+/// The progress bars for a day: one for the day as a whole, counting parts,
+/// with a bar nested beneath it for the part being solved.
+struct Bars {
+    multi: MultiProgress,
+    day: ProgressBar,
+}
+
+impl Bars {
+    fn new(solution: &dyn Solution, parts: usize) -> Self {
+        let multi = MultiProgress::new();
+        let style =
+            ProgressStyle::with_template("{prefix:>8} {wide_bar} {pos}/{len} {msg}").unwrap();
+        let day = multi.add(
+            ProgressBar::new(parts as u64)
+                .with_style(style)
+                .with_prefix(format!("Day {}", solution.day()))
+                .with_message(solution.title()),
+        );
+        Self { multi, day }
+    }
+
+    fn part(&self, label: String) -> ProgressBar {
+        let style =
+            ProgressStyle::with_template("{prefix:>8}   {wide_bar} {pos}/{len} {msg}").unwrap();
+        self.multi.insert_after(
+            &self.day,
+            ProgressBar::no_length()
+                .with_style(style)
+                .with_prefix(label),
+        )
+    }
+
+    /// Clears a part's bar and counts it as done. The bar is detached too,
+    /// as a part that timed out may carry on updating it.
+    fn done(&self, bar: &ProgressBar, parts: u64) {
+        bar.finish_and_clear();
+        self.multi.remove(bar);
+        self.day.inc(parts);
+    }
+}
+
+fn measure<T>(options: Options, mut f: impl FnMut() -> Result<T>) -> Step<T> {
+    let start = Instant::now();
+    let res = isolate::catch(|| -> Result<(T, Timing)> {
+        let mut timed = || -> Result<(T, Duration)> {
+            let start = Instant::now();
+            let value = f()?;
+            Ok((value, Instant::now().duration_since(start)))
+        };
+        let ((value, duration), mem) = match options.mem {
+            true => {
+                let (res, mem) = alloc::track(&mut timed);
+                (res?, Some(mem))
+            }
+            false => (timed()?, None),
+        };
+        Ok(match options.bench {
+            // The run above doubles as the first warmup.
+            Some(runs) => {
+                let (value, stats) = bench::bench(runs, &mut f)?;
+                let timing = Timing {
+                    duration: stats.median,
+                    bench: Some(stats),
+                    mem,
+                };
+                (value, timing)
+            }
+            None => {
+                let timing = Timing {
+                    duration,
+                    bench: None,
+                    mem,
+                };
+                (value, timing)
+            }
+        })
+    });
+    let elapsed = Instant::now().duration_since(start);
+    match res {
+        Ok(Ok((value, timing))) => Step {
+            result: Ok(value),
+            timing,
+        },
+        Ok(Err(e)) => Step::failed(Failure::Error(format!("{:#}", e)), elapsed),
+        Err(panic) => Step::failed(Failure::Error(panic), elapsed),
+    }
+}
+
+/// Runs `f` on a new thread, giving up on it after `timeout`. A worker that
+/// overruns is left to finish in the background.
+fn on_worker<R: Send + 'static>(
+    name: String,
+    timeout: Duration,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, (Failure, Duration)> {
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+    let spawned = thread::Builder::new().name(name).spawn(move || {
+        let _ = tx.send(f());
+    });
+    if let Err(e) = spawned {
+        let msg = format!("cannot spawn worker thread: {}", e);
+        return Err((Failure::Error(msg), Duration::ZERO));
+    }
+
+    rx.recv_timeout(timeout).map_err(|e| {
+        let elapsed = Instant::now().duration_since(start);
+        match e {
+            RecvTimeoutError::Timeout => (Failure::TimedOut, elapsed),
+            RecvTimeoutError::Disconnected => (
+                Failure::Error("worker thread exited without a result".to_owned()),
+                elapsed,
+            ),
+        }
+    })
+}
+
+impl Runner<'_> {
+    fn parse(&self, solution: &'static dyn Solution, input: &Arc<[u8]>) -> Step<Box<Puzzle>> {
+        let options = self.options;
+        let Some(timeout) = options.timeout else {
+            return measure(options, || solution.parse(input));
+        };
+
+        let input = input.clone();
+        let name = format!("day {} parse", solution.day());
+        on_worker(name, timeout, move || {
+            measure(options, || solution.parse(&input))
+        })
+        .unwrap_or_else(|(failure, elapsed)| Step::failed(failure, elapsed))
+    }
+
+    /// Runs `f` on the puzzle, handing the puzzle back unless it was lost
+    /// along with a worker thread that timed out.
+    fn with_puzzle<T: Send + 'static>(
+        &self,
+        name: String,
+        puzzle: Box<Puzzle>,
+        f: impl Fn(&Puzzle) -> Result<T> + Send + 'static,
+    ) -> (Step<T>, Option<Box<Puzzle>>) {
+        let options = self.options;
+        let Some(timeout) = options.timeout else {
+            let step = measure(options, || f(&*puzzle));
+            return (step, Some(puzzle));
+        };
+
+        match on_worker(name, timeout, move || {
+            let step = measure(options, || f(&*puzzle));
+            (step, puzzle)
+        }) {
+            Ok((step, puzzle)) => (step, Some(puzzle)),
+            Err((failure, elapsed)) => (Step::failed(failure, elapsed), None),
+        }
+    }
+
+    /// Solves a part with its default implementation, or with the named
+    /// variant.
+    fn solve(
+        &self,
+        solution: &'static dyn Solution,
+        (part, variant): (Part, Option<&'static str>),
+        puzzle: Box<Puzzle>,
+        ctx: &Arc<Context>,
+    ) -> (Step<Answer>, Option<Box<Puzzle>>) {
+        let mut name = format!("day {} part {}", solution.day(), part.number());
+        if let Some(variant) = variant {
+            name += &format!(" ({})", variant);
+        }
+        let ctx = ctx.clone();
+        self.with_puzzle(name, puzzle, move |p| match variant {
+            Some(variant) => solution.solve_variant(part, variant, p, &ctx),
+            None => solution.solve(part, p, &ctx),
+        })
+    }
+
+    /// Solves both parts in one go, giving each the timing of the whole.
+    fn solve_both(
+        &self,
+        solution: &'static dyn Solution,
+        puzzle: Box<Puzzle>,
+        ctx: &Arc<Context>,
+    ) -> [(Part, Step<Answer>); 2] {
+        let name = format!("day {} both parts", solution.day());
+        let ctx = ctx.clone();
+        let (step, _) = self.with_puzzle(name, puzzle, move |p| solution.solve_both(p, &ctx));
+        let (one, two) = match step.result {
+            Ok((one, two)) => (Ok(one), Ok(two)),
+            Err(failure) => (Err(failure.clone()), Err(failure)),
+        };
+        [
+            (
+                Part::One,
+                Step {
+                    result: one,
+                    timing: step.timing,
+                },
+            ),
+            (
+                Part::Two,
+                Step {
+                    result: two,
+                    timing: step.timing,
+                },
+            ),
+        ]
+    }
+
+    /// The parameters for an input: its sidecar file, if any, overridden by
+    /// those given on the command line.
+    fn context(&self, source: &Source) -> Result<Context> {
+        let mut ctx = match source {
+            Source::File(path) => Context::load(path)?,
+            Source::Stdin => Context::default(),
+        };
+        for (key, value) in self.params {
+            ctx.set(key, value);
+        }
+        Ok(ctx)
+    }
+
+    fn record(
+        &self,
+        (day, part, input): (u32, Part, &str),
+        source: &Source,
+        parse: Option<Duration>,
+        step: Step<Answer>,
+    ) -> Record {
+        // Inputs given by path are still checked if they're one of the day's.
+        let name = source.name(day).unwrap_or(input);
+        let mut record = Record {
+            day,
+            part,
+            variant: None,
+            input: source.to_string(),
+            answer: Answer::Unsolved,
+            duration: Some(step.timing.duration),
+            parse,
+            combined: false,
+            cached: false,
+            baseline: None,
+            status: Status::Error,
+            expected: self.answers.and_then(|a| a.get(day, part, name)).cloned(),
+            bench: step.timing.bench,
+            mem: step.timing.mem,
+            error: None,
+        };
+        match step.result {
+            Ok(answer) => {
+                let verdict = self.answers.map(|a| a.check(day, part, name, &answer));
+                record.status = Status::new(&answer, verdict);
+                record.answer = answer;
+            }
+            Err(Failure::Error(msg)) => record.error = Some(msg),
+            Err(Failure::TimedOut) => record.status = Status::Timeout,
+        }
+        record
+    }
+
+    /// Caches the answers in `records` that were solved successfully, under
+    /// their part's key.
+    fn remember(&self, keys: &BTreeMap<Part, String>, records: &[Record]) {
+        let Some(cache) = self.cache else {
+            return;
+        };
+        for record in records {
+            if record.cached || !record.answer.is_solved() || record.status.is_failure() {
+                continue;
+            }
+            if let Some(key) = keys.get(&record.part) {
+                let input = record.input.as_str();
+                cache.insert(
+                    key.clone(),
+                    (record.day, record.part, input),
+                    &record.answer,
+                );
+            }
+        }
+    }
+
+    /// The implementations to run on an input: the default one for each part
+    /// and, when comparing variants, each of the part's variants after it.
+    fn jobs(
+        &self,
+        solution: &'static dyn Solution,
+        parts: &[Part],
+    ) -> Vec<(Part, Option<&'static str>)> {
+        parts
+            .iter()
+            .flat_map(|&part| {
+                let variants = match self.options.variants {
+                    true => solution.variants(part),
+                    false => &[],
+                };
+                iter::once((part, None)).chain(variants.iter().map(move |&v| (part, Some(v))))
+            })
+            .collect()
+    }
+
+    /// Runs a day's plan, parsing each input once and sharing the puzzle
+    /// between its parts, or solving them together when the day can.
+    /// Failures, including unreadable inputs, are reported in the returned
+    /// records rather than cutting the run short.
+    pub fn run_day(&self, solution: &'static dyn Solution, plan: &Plan) -> Vec<InputRun> {
+        let day = solution.day();
+        let mut runs = vec![];
+        let bars = self.options.progress.then(|| {
+            Bars::new(
+                solution,
+                plan.iter()
+                    .map(|(_, parts)| self.jobs(solution, parts).len())
+                    .sum(),
+            )
+        });
+        // The context to solve with, reporting progress on a bar of its own
+        // when bars are drawn.
+        let with_bar = |ctx: &Context, label: String| match &bars {
+            Some(bars) => {
+                let bar = bars.part(label);
+                let ctx = ctx.with_progress(Progress::new(bar.clone()));
+                (Arc::new(ctx), Some(bar))
+            }
+            None => (Arc::new(ctx.clone()), None),
+        };
+        let done = |bar: Option<ProgressBar>, parts: usize| {
+            if let Some(bars) = &bars {
+                match bar {
+                    Some(bar) => bars.done(&bar, parts as u64),
+                    None => bars.day.inc(parts as u64),
+                }
+            }
+        };
+        for (input, parts) in plan {
+            let source = Source::resolve(day, input);
+            let mut run = InputRun {
+                day,
+                input: input.clone(),
+                parse: None,
+                records: vec![],
+            };
+            let failed = |jobs: &[(Part, Option<&'static str>)], failure: Failure| {
+                done(None, jobs.len());
+                jobs.iter()
+                    .map(|&(part, variant)| {
+                        let step = Step::failed(failure.clone(), Duration::ZERO);
+                        let mut record = self.record((day, part, input), &source, None, step);
+                        record.variant = self
+                            .options
+                            .variants
+                            .then_some(variant.unwrap_or(DEFAULT_VARIANT));
+                        record
+                    })
+                    .collect()
+            };
+
+            let data: Arc<[u8]> = match source.read() {
+                Ok(data) => data.into(),
+                Err(e) => {
+                    let jobs = self.jobs(solution, parts);
+                    run.records = failed(&jobs, Failure::Error(format!("{:#}", e)));
+                    runs.push(run);
+                    continue;
+                }
+            };
+
+            let ctx = match self.context(&source) {
+                Ok(ctx) => ctx,
+                Err(e) => {
+                    let jobs = self.jobs(solution, parts);
+                    run.records = failed(&jobs, Failure::Error(format!("{:#}", e)));
+                    runs.push(run);
+                    continue;
+                }
+            };
+
+            // Parts with a cached answer needn't be solved, and if that's all
+            // of them, the input needn't even be parsed.
+            let keys: BTreeMap<Part, String> = match self.cache {
+                Some(_) => parts
+                    .iter()
+                    .filter_map(|&part| Some((part, cache::key(day, part, &data, &ctx)?)))
+                    .collect(),
+                None => BTreeMap::new(),
+            };
+            let mut parts = parts.clone();
+            for (&part, key) in &keys {
+                let Some(answer) = self.cache.and_then(|c| c.get(key)) else {
+                    continue;
+                };
+                parts.retain(|&p| p != part);
+                let step = Step {
+                    result: Ok(answer),
+                    timing: Timing::default(),
+                };
+                let mut record = self.record((day, part, input), &source, None, step);
+                record.cached = true;
+                record.duration = None;
+                run.records.push(record);
+                done(None, 1);
+            }
+            if parts.is_empty() {
+                runs.push(run);
+                continue;
+            }
+            let jobs = self.jobs(solution, &parts);
+
+            let parsed = self.parse(solution, &data);
+            run.parse = Some(parsed.timing);
+            let parse_time = Some(parsed.timing.duration);
+            let mut puzzle = match parsed.result {
+                Ok(puzzle) => Some(puzzle),
+                Err(failure) => {
+                    let failure = match failure {
+                        Failure::Error(msg) => Failure::Error(format!("parsing: {}", msg)),
+                        Failure::TimedOut => Failure::TimedOut,
+                    };
+                    run.records.extend(failed(&jobs, failure));
+                    run.records.sort_by_key(|r| r.part);
+                    runs.push(run);
+                    continue;
+                }
+            };
+
+            if let Some(puzzle) = puzzle.take_if(|_| {
+                solution.has_solve_both()
+                    && !self.options.variants
+                    && Part::ALL.iter().all(|p| parts.contains(p))
+            }) {
+                let (ctx, bar) = with_bar(&ctx, "Parts".to_owned());
+                let steps = self.solve_both(solution, puzzle, &ctx);
+                done(bar, steps.len());
+                for (part, step) in steps {
+                    let mut record = self.record((day, part, input), &source, parse_time, step);
+                    record.combined = true;
+                    run.records.push(record);
+                }
+                self.remember(&keys, &run.records);
+                runs.push(run);
+                continue;
+            }
+
+            for &(part, variant) in &jobs {
+                let mut label = format!("Part {}", part.number());
+                if let Some(variant) = variant {
+                    label += &format!(" {}", variant);
+                }
+                let (ctx, bar) = with_bar(&ctx, label);
+                let step = match puzzle.take() {
+                    Some(p) => {
+                        let (step, p) = self.solve(solution, (part, variant), p, &ctx);
+                        puzzle = p;
+                        step
+                    }
+                    // An earlier part timed out with the puzzle, so parse it
+                    // again.
+                    None => match self.parse(solution, &data).result {
+                        Ok(p) => {
+                            let (step, p) = self.solve(solution, (part, variant), p, &ctx);
+                            puzzle = p;
+                            step
+                        }
+                        Err(failure) => Step::failed(failure, Duration::ZERO),
+                    },
+                };
+                done(bar, 1);
+                let mut record = self.record((day, part, input), &source, parse_time, step);
+                record.variant = self
+                    .options
+                    .variants
+                    .then_some(variant.unwrap_or(DEFAULT_VARIANT));
+                run.records.push(record);
+            }
+            if self.options.variants {
+                check_agreement(&mut run.records);
+            }
+            self.remember(&keys, &run.records);
+            run.records.sort_by_key(|r| r.part);
+            runs.push(run);
+        }
+        if let Some(bars) = bars {
+            bars.day.finish_and_clear();
+        }
+        runs
+    }
+
+    /// Runs every day, handing each day's results to `report` in the order
+    /// given. With `jobs`, days are solved concurrently on a pool of that many
+    /// threads (0 for one per core), and results are held back until every
+    /// earlier day has been reported.
+    pub fn run_all(
+        &self,
+        days: &[(&'static dyn Solution, Plan)],
+        jobs: Option<usize>,
+        mut report: impl FnMut(Vec<InputRun>),
+    ) -> Result<()> {
+        let Some(jobs) = jobs else {
+            for (solution, plan) in days {
+                report(self.run_day(*solution, plan));
+            }
+            return Ok(());
+        };
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+        thread::scope(|s| {
+            let (tx, rx) = mpsc::channel();
+            s.spawn(|| {
+                pool.install(|| {
+                    days.par_iter()
+                        .enumerate()
+                        .for_each_with(tx, |tx, (i, (solution, plan))| {
+                            let _ = tx.send((i, self.run_day(*solution, plan)));
+                        })
+                })
+            });
+
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+            for (i, runs) in rx {
+                pending.insert(i, runs);
+                while let Some(runs) = pending.remove(&next) {
+                    report(runs);
+                    next += 1;
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+use aoc_2025::input;
+
+/// A new day's module: a line parser built with nom and parts left unsolved.
+const TEMPLATE: &str = r#"use std::io::BufRead;
+
+use anyhow::{Result, anyhow};
+use nom::{
+    Parser,
+    character::complete::{digit1, space1},
+    combinator::{all_consuming, map_res},
+    multi::separated_list1,
+};
+
+use crate::{answer::Answer, solution::solution};
+
+solution!({day}, {title});
+
+fn parse_line(s: &str) -> Result<Vec<u64>> {
+    let res: Result<_, nom::Err<()>> =
+        all_consuming(separated_list1(space1, map_res(digit1, str::parse))).parse(s);
+    let (_, nums) = res.map_err(|_| anyhow!("malformed line '{}'", s))?;
+    Ok(nums)
+}
+
+pub fn parse(input: impl BufRead) -> Result<Vec<Vec<u64>>> {
+    input.lines().map(|line| parse_line(&line?)).collect()
+}
+
+pub fn part1(_lines: &[Vec<u64>]) -> Result<Answer> {
+    Ok(Answer::Unsolved)
+}
+
+pub fn part2(_lines: &[Vec<u64>]) -> Result<Answer> {
+    Ok(Answer::Unsolved)
+}
+"#;
+
+/// Creates the module and input placeholders for a new day, and appends
+/// commented-out entries for its answers to the answers file. Nothing is
+/// written if the module or inputs already exist.
+pub fn new(day: u32, title: &str, answers: &Path) -> Result<()> {
+    if !Path::new("src").is_dir() {
+        bail!("no src directory here; run from the crate root");
+    }
+
+    let module = PathBuf::from(format!("src/day{}.rs", day));
+    let dir = input::dir(day);
+    let inputs = [dir.join("test.txt"), dir.join("input.txt")];
+    for path in inputs.iter().chain([&module]) {
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+    }
+
+    // Open the answers file up front so a bad path fails before anything is
+    // created.
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(answers)
+        .with_context(|| format!("cannot open {}", answers.display()))?;
+
+    let code = TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{title}", &format!("{:?}", title));
+    fs::write(&module, code).with_context(|| format!("cannot write {}", module.display()))?;
+    println!("created {}", module.display());
+
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    for path in &inputs {
+        fs::write(path, "").with_context(|| format!("cannot write {}", path.display()))?;
+        println!("created {}", path.display());
+    }
+
+    writeln!(file)?;
+    for name in ["test.txt", "input.txt"] {
+        for part in 1..=2 {
+            writeln!(file, "# {} {} {} <answer>", day, part, name)?;
+        }
+    }
+    println!("added day {} to {}", day, answers.display());
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::{ops::Range, str::FromStr};
+
+use anyhow::{Context, Error, Result, bail};
+
+use aoc_2025::solution::{self, Part, Solution};
+
+/// A command-line day selector: a day (`8`), an inclusive (`6..=9`) or
+/// exclusive (`6..10`) range of days, optionally limited to one part (`10:1`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    days: Range<u32>,
+    part: Option<Part>,
+}
+
+impl Selector {
+    fn matches(&self, day: u32, part: Part) -> bool {
+        self.days.contains(&day) && self.part.is_none_or(|p| p == part)
+    }
+}
+
+impl FromStr for Selector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (days, part) = match s.split_once(':') {
+            Some((days, "1")) => (days, Some(Part::One)),
+            Some((days, "2")) => (days, Some(Part::Two)),
+            Some((_, part)) => bail!("invalid part '{}'", part),
+            None => (s, None),
+        };
+        let day = |s: &str| -> Result<u32> {
+            s.parse()
+                .with_context(|| format!("invalid day '{}' in '{}'", s, days))
+        };
+        let after = |s: &str| -> Result<u32> {
+            let day = day(s)?;
+            day.checked_add(1)
+                .with_context(|| format!("day {} is out of range", day))
+        };
+        let range = if let Some((start, end)) = days.split_once("..=") {
+            day(start)?..after(end)?
+        } else if let Some((start, end)) = days.split_once("..") {
+            day(start)?..day(end)?
+        } else {
+            day(days)?..after(days)?
+        };
+        if range.is_empty() {
+            bail!(
+                "'{}' selects no days; the start must come before the end",
+                days
+            );
+        }
+        Ok(Self { days: range, part })
+    }
+}
+
+/// Picks the registered solutions and parts to run: those matching any of
+/// `include` (everything if it is empty) and none of `exclude`, limited to
+/// `parts`. A selector naming a single day that has no solution is an error.
+pub fn select(
+    include: &[Selector],
+    exclude: &[Selector],
+    parts: &[Part],
+) -> Result<Vec<(&'static dyn Solution, Vec<Part>)>> {
+    for selector in include {
+        let day = selector.days.start;
+        if selector.days.len() == 1 && solution::get(day).is_none() {
+            bail!("no solution for day {}", day);
+        }
+    }
+
+    let selected: Vec<_> = solution::all()
+        .map(|solution| {
+            let day = solution.day();
+            let parts = parts
+                .iter()
+                .copied()
+                .filter(|&p| include.is_empty() || include.iter().any(|s| s.matches(day, p)))
+                .filter(|&p| !exclude.iter().any(|s| s.matches(day, p)))
+                .collect();
+            (solution, parts)
+        })
+        .filter(|(_, parts): &(_, Vec<_>)| !parts.is_empty())
+        .collect();
+    if selected.is_empty() {
+        bail!("no days selected");
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector(s: &str) -> Selector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_days_ranges_and_parts() {
+        assert_eq!(selector("8").days, 8..9);
+        assert_eq!(selector("6..=9").days, 6..10);
+        assert_eq!(selector("6..10").days, 6..10);
+        let single = selector("10:1");
+        assert_eq!(single.days, 10..11);
+        assert_eq!(single.part, Some(Part::One));
+        assert!(single.matches(10, Part::One));
+        assert!(!single.matches(10, Part::Two));
+    }
+
+    #[test]
+    fn rejects_bad_selectors() {
+        assert!("9..3".parse::<Selector>().is_err());
+        assert!("10:3".parse::<Selector>().is_err());
+        assert!("4294967295..=4294967295".parse::<Selector>().is_err());
+    }
+}
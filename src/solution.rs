@@ -0,0 +1,304 @@
+use std::{any::Any, borrow::Borrow, fmt};
+
+use anyhow::{Result, bail};
+
+use crate::{Input, answer::Answer, context::Context};
+
+/// One of the two halves of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Part {}", self.number())
+    }
+}
+
+/// A day's parsed input, as produced by [`Solution::parse`].
+pub type Puzzle = dyn Any + Send;
+
+/// A day's puzzle, as seen by the runner.
+///
+/// Day modules don't implement this by hand; they expose a `parse` function
+/// generic over [`BufRead`](std::io::BufRead), plus `part1` and `part2`
+/// functions taking a reference to its output (and, if they need puzzle
+/// parameters, the [`Context`]) and returning anything convertible into an
+/// [`Answer`], and invoke [`solution!`] to generate the impl. Days whose
+/// parts share most of their work can also expose a `solve_both` function
+/// returning both answers at once, and days can register alternative
+/// implementations of a part as named variants.
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn parse(&self, input: Input) -> Result<Box<Puzzle>>;
+    /// Panics if `puzzle` was not parsed by this day.
+    fn part1(&self, puzzle: &Puzzle, ctx: &Context) -> Result<Answer>;
+    /// Panics if `puzzle` was not parsed by this day.
+    fn part2(&self, puzzle: &Puzzle, ctx: &Context) -> Result<Answer>;
+
+    fn solve(&self, part: Part, puzzle: &Puzzle, ctx: &Context) -> Result<Answer> {
+        match part {
+            Part::One => self.part1(puzzle, ctx),
+            Part::Two => self.part2(puzzle, ctx),
+        }
+    }
+
+    /// Whether [`solve_both`](Solution::solve_both) does less work than
+    /// solving the parts one after the other.
+    fn has_solve_both(&self) -> bool {
+        false
+    }
+
+    /// Solves both parts, returning their answers in order.
+    fn solve_both(&self, puzzle: &Puzzle, ctx: &Context) -> Result<(Answer, Answer)> {
+        Ok((self.part1(puzzle, ctx)?, self.part2(puzzle, ctx)?))
+    }
+
+    /// The names of the alternative implementations of a part, besides the
+    /// one [`solve`](Solution::solve) uses.
+    fn variants(&self, _part: Part) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Solves a part with the named variant, one of those listed by
+    /// [`variants`](Solution::variants).
+    fn solve_variant(
+        &self,
+        part: Part,
+        name: &str,
+        _puzzle: &Puzzle,
+        _ctx: &Context,
+    ) -> Result<Answer> {
+        bail!("day {} {} has no variant '{}'", self.day(), part, name)
+    }
+}
+
+/// Recovers the concrete puzzle type from a day's `parse` function, so that
+/// [`solution!`] doesn't need to be told it.
+#[doc(hidden)]
+pub fn downcast<T: 'static>(_parse: fn(Input<'static>) -> Result<T>, puzzle: &Puzzle) -> &T {
+    puzzle
+        .downcast_ref()
+        .expect("puzzle was parsed by a different day")
+}
+
+#[doc(hidden)]
+pub struct Plain;
+#[doc(hidden)]
+pub struct WithContext;
+
+/// A day's `part1`, `part2` or `solve_both` function, with or without a
+/// [`Context`] parameter. `M` tells the two signatures apart.
+#[doc(hidden)]
+pub trait PartFn<T: ?Sized, M> {
+    type Output;
+
+    fn call(&self, puzzle: &T, ctx: &Context) -> Result<Self::Output>;
+}
+
+impl<T: ?Sized, R, F: Fn(&T) -> Result<R>> PartFn<T, Plain> for F {
+    type Output = R;
+
+    fn call(&self, puzzle: &T, _ctx: &Context) -> Result<R> {
+        self(puzzle)
+    }
+}
+
+impl<T: ?Sized, R, F: Fn(&T, &Context) -> Result<R>> PartFn<T, WithContext> for F {
+    type Output = R;
+
+    fn call(&self, puzzle: &T, ctx: &Context) -> Result<R> {
+        self(puzzle, ctx)
+    }
+}
+
+/// Calls a day's part function on a puzzle produced by the day's `parse`,
+/// letting the function take any type the parsed puzzle can be borrowed as
+/// (a slice for a `Vec`, say).
+#[doc(hidden)]
+pub fn call<P: Borrow<T> + 'static, T: ?Sized, M, F: PartFn<T, M>>(
+    parse: fn(Input<'static>) -> Result<P>,
+    f: F,
+    puzzle: &Puzzle,
+    ctx: &Context,
+) -> Result<F::Output> {
+    f.call(downcast(parse, puzzle).borrow(), ctx)
+}
+
+/// Like [`call`], for a part function returning anything convertible into an
+/// [`Answer`].
+#[doc(hidden)]
+pub fn call_part<P: Borrow<T> + 'static, T: ?Sized, M, F: PartFn<T, M>>(
+    parse: fn(Input<'static>) -> Result<P>,
+    f: F,
+    puzzle: &Puzzle,
+    ctx: &Context,
+) -> Result<Answer>
+where
+    F::Output: Into<Answer>,
+{
+    call(parse, f, puzzle, ctx).map(Into::into)
+}
+
+/// Declares the `Day` unit struct for the enclosing day module, implementing
+/// [`Solution`] in terms of the module's `parse`, `part1` and `part2`
+/// functions. A part the module doesn't have yet is reported unsolved; the
+/// build script notices it's missing and generates the stub. Passing
+/// `solve_both` as a third argument also wires up the module's `solve_both`
+/// function.
+///
+/// A trailing `variants { 2: [dfs = part2_dfs] }` registers other functions
+/// with the signature of a part's as named variants of that part.
+macro_rules! solution {
+    ($day:literal, $title:literal) => {
+        $crate::solution::solution!(@impl $day, $title, {});
+    };
+    ($day:literal, $title:literal, solve_both) => {
+        $crate::solution::solution!(@impl $day, $title, {
+            $crate::solution::solution!(@solve_both);
+        });
+    };
+    ($day:literal, $title:literal, variants { $($variants:tt)* }) => {
+        $crate::solution::solution!(@impl $day, $title, {
+            $crate::solution::solution!(@variants $($variants)*);
+        });
+    };
+    ($day:literal, $title:literal, solve_both, variants { $($variants:tt)* }) => {
+        $crate::solution::solution!(@impl $day, $title, {
+            $crate::solution::solution!(@solve_both);
+            $crate::solution::solution!(@variants $($variants)*);
+        });
+    };
+    (@solve_both) => {
+        fn has_solve_both(&self) -> bool {
+            true
+        }
+
+        fn solve_both(
+            &self,
+            puzzle: &$crate::solution::Puzzle,
+            ctx: &$crate::context::Context,
+        ) -> ::anyhow::Result<($crate::answer::Answer, $crate::answer::Answer)> {
+            let (part1, part2) = $crate::solution::call(parse, solve_both, puzzle, ctx)?;
+            Ok((part1.into(), part2.into()))
+        }
+    };
+    (@variants $($part:literal: [$($name:ident = $f:ident),* $(,)?]),* $(,)?) => {
+        fn variants(&self, part: $crate::solution::Part) -> &'static [&'static str] {
+            match part.number() {
+                $($part => &[$(stringify!($name)),*],)*
+                _ => &[],
+            }
+        }
+
+        fn solve_variant(
+            &self,
+            part: $crate::solution::Part,
+            name: &str,
+            puzzle: &$crate::solution::Puzzle,
+            ctx: &$crate::context::Context,
+        ) -> ::anyhow::Result<$crate::answer::Answer> {
+            $($(
+                if part.number() == $part && name == stringify!($name) {
+                    return $crate::solution::call(parse, $f, puzzle, ctx).map(Into::into);
+                }
+            )*)*
+            ::anyhow::bail!("day {} {} has no variant '{}'", self.day(), part, name)
+        }
+    };
+    (@impl $day:literal, $title:literal, { $($extra:tt)* }) => {
+        pub struct Day;
+
+        impl $crate::solution::Solution for Day {
+            fn day(&self) -> u32 {
+                $day
+            }
+
+            fn title(&self) -> &'static str {
+                $title
+            }
+
+            fn parse(
+                &self,
+                input: $crate::Input,
+            ) -> ::anyhow::Result<Box<$crate::solution::Puzzle>> {
+                Ok(Box::new(parse(input)?))
+            }
+
+            fn part1(
+                &self,
+                puzzle: &$crate::solution::Puzzle,
+                ctx: &$crate::context::Context,
+            ) -> ::anyhow::Result<$crate::answer::Answer> {
+                // Generated by the build script, which stubs out missing parts.
+                (include!(concat!(env!("OUT_DIR"), "/day", $day, "_part1.rs")))(puzzle, ctx)
+            }
+
+            fn part2(
+                &self,
+                puzzle: &$crate::solution::Puzzle,
+                ctx: &$crate::context::Context,
+            ) -> ::anyhow::Result<$crate::answer::Answer> {
+                (include!(concat!(env!("OUT_DIR"), "/day", $day, "_part2.rs")))(puzzle, ctx)
+            }
+
+            $($extra)*
+        }
+    };
+}
+pub(crate) use solution;
+
+/// Declares the day modules and collects their solutions, along with each
+/// module's source, into the registry. Invoked from code generated by the
+/// build script, which finds the modules.
+macro_rules! register {
+    ($($module:ident = $path:literal),* $(,)?) => {
+        $(
+            #[path = $path]
+            pub mod $module;
+        )*
+
+        pub(crate) static SOLUTIONS: &[(&dyn $crate::solution::Solution, &str)] =
+            &[$((&$module::Day, include_str!($path))),*];
+    };
+}
+pub(crate) use register;
+
+/// Every registered solution, in day order.
+pub fn all() -> impl Iterator<Item = &'static dyn Solution> {
+    let mut solutions: Vec<_> = crate::SOLUTIONS.iter().map(|&(s, _)| s).collect();
+    solutions.sort_by_key(|s| s.day());
+    solutions.into_iter()
+}
+
+pub fn get(day: u32) -> Option<&'static dyn Solution> {
+    all().find(|s| s.day() == day)
+}
+
+/// A hash of the crate's source other than the day modules, as it was when
+/// compiled.
+pub fn shared_source_hash() -> u64 {
+    crate::SHARED_SOURCE_HASH
+}
+
+/// The source of a day's module, as it was when compiled.
+pub fn source(day: u32) -> Option<&'static str> {
+    crate::SOLUTIONS
+        .iter()
+        .find(|(s, _)| s.day() == day)
+        .map(|&(_, source)| source)
+}
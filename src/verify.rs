@@ -0,0 +1,99 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use nom::{
+    Parser,
+    bytes::is_not,
+    character::complete::{digit1, one_of, space1},
+    combinator::{all_consuming, map_res, recognize, rest},
+};
+
+use aoc_2025::{answer::Answer, solution::Part};
+
+/// How a part's answer compares to the answers file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// There is no expected answer to compare against.
+    Unknown,
+}
+
+#[derive(Debug)]
+struct Entry {
+    day: u32,
+    part: Part,
+    input: String,
+    answer: Answer,
+}
+
+/// Known-good answers, keyed by day, part and input file name.
+///
+/// Each non-empty line of the file reads `<day> <part> <input file> <answer>`;
+/// lines starting with `#` are comments.
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: Vec<Entry>,
+}
+
+fn parse_line(s: &str) -> Result<Entry> {
+    let res: Result<_, nom::Err<()>> = all_consuming((
+        map_res(recognize(digit1), str::parse),
+        space1,
+        one_of("12").map(|c| if c == '1' { Part::One } else { Part::Two }),
+        space1,
+        is_not(" \t"),
+        space1,
+        rest,
+    ))
+    .parse(s);
+
+    let (_, (day, _, part, _, input, _, answer)) =
+        res.map_err(|_| anyhow!("malformed answer line '{}'", s))?;
+    Ok(Entry {
+        day,
+        part,
+        input: input.to_owned(),
+        answer: answer.trim_end().parse()?,
+    })
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("cannot read answers file {}", path.display()))?;
+        let entries = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+            .map(|(i, l)| parse_line(l).with_context(|| format!("{}:{}", path.display(), i + 1)))
+            .collect::<Result<_>>()?;
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, day: u32, part: Part, input: &str) -> Option<&Answer> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.input == input)
+            .map(|e| &e.answer)
+    }
+
+    /// The input files with a recorded answer for `day`, in file order.
+    pub fn inputs(&self, day: u32) -> Vec<&str> {
+        let mut inputs: Vec<&str> = vec![];
+        for e in self.entries.iter().filter(|e| e.day == day) {
+            if !inputs.contains(&e.input.as_str()) {
+                inputs.push(&e.input);
+            }
+        }
+        inputs
+    }
+
+    pub fn check(&self, day: u32, part: Part, input: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, input) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+        }
+    }
+}
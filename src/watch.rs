@@ -0,0 +1,74 @@
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::color;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of every file under the watched paths.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Polls a set of files and directories for changes. Directories are watched
+/// one level deep, so adding or removing a file also counts as a change.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    last: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(mut paths: Vec<PathBuf>) -> Self {
+        paths.sort();
+        paths.dedup();
+        let mut watcher = Self {
+            paths,
+            last: vec![],
+        };
+        watcher.last = watcher.snapshot();
+        watcher
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let modified = |path: PathBuf| {
+            let time = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, time)
+        };
+        let mut snapshot = vec![];
+        for path in &self.paths {
+            match fs::read_dir(path) {
+                Ok(entries) => snapshot.extend(entries.flatten().map(|e| modified(e.path()))),
+                Err(_) => snapshot.push(modified(path.clone())),
+            }
+        }
+        snapshot.sort();
+        snapshot
+    }
+
+    /// Blocks until something under the watched paths changes.
+    pub fn wait(&mut self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let snapshot = self.snapshot();
+            if snapshot != self.last {
+                self.last = snapshot;
+                return;
+            }
+        }
+    }
+}
+
+/// Clears the terminal, unless output isn't being decorated, in which case
+/// runs are just separated by a blank line.
+pub fn clear_screen() {
+    match color::enabled() {
+        true => print!("\x1b[2J\x1b[H"),
+        false => println!(),
+    }
+}